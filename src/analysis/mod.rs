@@ -0,0 +1,4 @@
+pub mod nft_loans;
+pub mod rarity;
+pub mod royalty_compliance;
+pub mod wash_trading;
@@ -0,0 +1,520 @@
+//! # NFT Loans
+//!
+//! Normalizes the loan events of the NFT lending protocols into one [`LoanEvent`] shape, and
+//! rebuilds the loans of each collateral mint from them, whatever the protocol.
+//!
+//! Events are grouped into a loan by protocol and loan account, a group without a borrow event
+//! (an offer nobody took) is not a loan. The terms of a loan are the first known value across
+//! its events. Amounts are in lamports, rates in percent.
+use std::collections::{BTreeMap, HashMap};
+
+use crate::dimension_facts::protocols::citrus::loan_events::{
+    citrus_loan_events, CitrusLoanAction, CitrusLoanEvent, CitrusLoanEventsRequest,
+};
+use crate::dimension_facts::protocols::frakt::bonds_loan_events::{
+    frakt_bonds_loan_events, FraktBondAction, FraktBondsLoanEvent, FraktBondsLoanEventsRequest,
+};
+use crate::dimension_facts::protocols::frakt::loan_events::{
+    frakt_loan_events, FraktLoanAction, FraktLoanEvent, FraktLoanEventsRequest,
+};
+use crate::dimension_facts::protocols::sharky::apy::apy_to_apr;
+use crate::dimension_facts::protocols::sharky::loan_events::{
+    sharky_loan_events, SharkyLoanAction, SharkyLoanEvent, SharkyLoanEventsRequest,
+};
+use crate::fetch_all_pages;
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 86400.0;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum LendingProtocol {
+    Citrus,
+    Frakt,
+    FraktBonds,
+    Sharky,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum LoanEventKind {
+    /// Terms posted before the loan is funded: a lender offer, or a Frakt loan proposal
+    Offer,
+    /// Terms withdrawn before the loan is funded
+    CancelOffer,
+    /// The loan was funded and the collateral locked
+    Borrow,
+    /// The loan was paid back and the collateral released
+    Repay,
+    /// The loan was paid back with a new loan on the same collateral
+    Refinance,
+    /// The collateral was seized: claimed, foreclosed or liquidated
+    Default,
+    /// An instruction the lifecycle does not depend on, e.g. redeeming Frakt bond tokens
+    Other,
+}
+
+/// A loan event of any protocol.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct LoanEvent {
+    pub protocol: Option<LendingProtocol>,
+    pub kind: Option<LoanEventKind>,
+    pub transaction_id: Option<String>,
+    pub block_time: Option<usize>,
+    /// The account of the loan, the bond for Frakt bonds
+    pub loan: Option<String>,
+    pub lender: Option<String>,
+    pub borrower: Option<String>,
+    pub collateral_mint: Option<String>,
+    pub hello_moon_collection_id: Option<String>,
+    /// Amount lent, in lamports
+    pub principal: Option<u64>,
+    /// Annual percentage rate, in percent
+    pub apr: Option<f64>,
+    /// Duration of the loan, in seconds
+    pub duration: Option<u64>,
+}
+
+/// Rate of a loan paying `repay` back for `principal` after `duration` seconds, in percent.
+fn simple_apr(principal: Option<u64>, repay: Option<u64>, duration: Option<u64>) -> Option<f64> {
+    let principal = principal.filter(|principal| *principal > 0)?;
+    let duration = duration.filter(|duration| *duration > 0)?;
+    let interest = repay?.checked_sub(principal)? as f64 / principal as f64;
+    Some(interest * SECONDS_PER_YEAR / duration as f64 * 100.0)
+}
+
+impl From<&CitrusLoanEvent> for LoanEvent {
+    fn from(event: &CitrusLoanEvent) -> Self {
+        Self {
+            protocol: Some(LendingProtocol::Citrus),
+            kind: event.action_type.map(|action| match action {
+                CitrusLoanAction::Offer => LoanEventKind::Offer,
+                CitrusLoanAction::CancelOffer => LoanEventKind::CancelOffer,
+                CitrusLoanAction::Borrow => LoanEventKind::Borrow,
+                CitrusLoanAction::Repay => LoanEventKind::Repay,
+                CitrusLoanAction::Claim => LoanEventKind::Default,
+                CitrusLoanAction::Other => LoanEventKind::Other,
+            }),
+            transaction_id: event.transaction_id.clone(),
+            block_time: event.block_time,
+            loan: event.loan_account.clone(),
+            lender: event.lender.clone(),
+            borrower: event
+                .borrower
+                .clone()
+                .filter(|borrower| !borrower.is_empty()),
+            collateral_mint: event.collateral_mint.clone(),
+            hello_moon_collection_id: event.hello_moon_collection_id.clone(),
+            principal: event.loan_amount,
+            apr: event.apr(),
+            duration: event.duration,
+        }
+    }
+}
+
+impl From<&FraktLoanEvent> for LoanEvent {
+    fn from(event: &FraktLoanEvent) -> Self {
+        let duration = event.return_period_days.map(|days| days * 86400);
+        Self {
+            protocol: Some(LendingProtocol::Frakt),
+            kind: event.action_type.map(|action| match action {
+                FraktLoanAction::ProposeLoan => LoanEventKind::Offer,
+                FraktLoanAction::RejectLoan => LoanEventKind::CancelOffer,
+                FraktLoanAction::ApproveLoan => LoanEventKind::Borrow,
+                FraktLoanAction::PaybackLoan => LoanEventKind::Repay,
+                FraktLoanAction::LiquidateLoan => LoanEventKind::Default,
+                FraktLoanAction::Other => LoanEventKind::Other,
+            }),
+            transaction_id: event.transaction_id.clone(),
+            block_time: event.block_time,
+            loan: event.loan.clone(),
+            lender: event.liquidity_pool.clone(),
+            borrower: event.user.clone(),
+            collateral_mint: event.nft_mint.clone(),
+            hello_moon_collection_id: event.hello_moon_collection_id.clone(),
+            principal: event.loan_value,
+            apr: simple_apr(event.loan_value, event.repay_value, duration),
+            duration,
+        }
+    }
+}
+
+impl From<&FraktBondsLoanEvent> for LoanEvent {
+    fn from(event: &FraktBondsLoanEvent) -> Self {
+        let duration = event.duration().map(|duration| duration as u64);
+        Self {
+            protocol: Some(LendingProtocol::FraktBonds),
+            kind: event.action_type.map(|action| match action {
+                FraktBondAction::Borrow => LoanEventKind::Borrow,
+                FraktBondAction::Repay => LoanEventKind::Repay,
+                FraktBondAction::Liquidate => LoanEventKind::Default,
+                FraktBondAction::Redeem | FraktBondAction::Other => LoanEventKind::Other,
+            }),
+            transaction_id: event.transaction_id.clone(),
+            block_time: event.block_time,
+            loan: event.frakt_bond.clone(),
+            lender: event.lender.clone(),
+            borrower: event.borrower.clone(),
+            collateral_mint: event.collateral_mint.clone(),
+            hello_moon_collection_id: event.hello_moon_collection_id.clone(),
+            principal: event.borrowed_amount,
+            apr: simple_apr(event.borrowed_amount, event.repay_amount, duration),
+            duration,
+        }
+    }
+}
+
+impl From<&SharkyLoanEvent> for LoanEvent {
+    fn from(event: &SharkyLoanEvent) -> Self {
+        Self {
+            protocol: Some(LendingProtocol::Sharky),
+            kind: event.action_type.map(|action| match action {
+                SharkyLoanAction::OfferLoan => LoanEventKind::Offer,
+                SharkyLoanAction::RescindLoan => LoanEventKind::CancelOffer,
+                SharkyLoanAction::TakeLoan => LoanEventKind::Borrow,
+                SharkyLoanAction::RepayLoan => LoanEventKind::Repay,
+                SharkyLoanAction::ExtendLoan => LoanEventKind::Refinance,
+                SharkyLoanAction::ForecloseLoan => LoanEventKind::Default,
+                SharkyLoanAction::Other => LoanEventKind::Other,
+            }),
+            transaction_id: event.transaction_id.clone(),
+            block_time: event.block_time,
+            loan: event.loan.clone(),
+            lender: event.lender.clone(),
+            borrower: event
+                .borrower
+                .clone()
+                .filter(|borrower| !borrower.is_empty()),
+            collateral_mint: event.collateral_mint.clone(),
+            hello_moon_collection_id: event.hello_moon_collection_id.clone(),
+            principal: event.principal_lamports,
+            apr: event
+                .apy
+                .zip(event.duration_seconds)
+                .and_then(|(apy, duration_seconds)| apy_to_apr(apy, duration_seconds)),
+            duration: event.duration_seconds,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum LoanOutcome {
+    /// Funded and not closed yet
+    #[default]
+    Active,
+    Repaid,
+    Refinanced,
+    Defaulted,
+}
+
+/// A funded loan and the events it went through, in block time order.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct NftLoan {
+    pub protocol: Option<LendingProtocol>,
+    pub loan: String,
+    pub collateral_mint: Option<String>,
+    pub hello_moon_collection_id: Option<String>,
+    pub lender: Option<String>,
+    pub borrower: Option<String>,
+    /// Amount lent, in lamports
+    pub principal: Option<u64>,
+    /// Annual percentage rate, in percent
+    pub apr: Option<f64>,
+    /// Duration of the loan, in seconds
+    pub duration: Option<u64>,
+    /// Principal over the floor price of the collection, between 0 and 1 for a loan under floor
+    pub ltv: Option<f64>,
+    /// Block time of the borrow event
+    pub started_at: Option<usize>,
+    /// Block time of the event that closed the loan
+    pub closed_at: Option<usize>,
+    pub outcome: LoanOutcome,
+    pub events: Vec<LoanEvent>,
+}
+
+impl NftLoan {
+    /// Unix epoch time (in seconds) the loan is due at.
+    pub fn due_at(&self) -> Option<usize> {
+        Some(self.started_at? + self.duration? as usize)
+    }
+
+    /// Whether the loan is still active after its due time at `now`.
+    pub fn is_overdue(&self, now: usize) -> bool {
+        self.outcome == LoanOutcome::Active && self.due_at().is_some_and(|due_at| due_at < now)
+    }
+}
+
+/// Every loan taken against a collateral mint, ordered by start.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollateralTimeline {
+    pub collateral_mint: String,
+    pub loans: Vec<NftLoan>,
+}
+
+impl CollateralTimeline {
+    pub fn defaults(&self) -> usize {
+        self.loans
+            .iter()
+            .filter(|loan| loan.outcome == LoanOutcome::Defaulted)
+            .count()
+    }
+}
+
+fn build_loan(
+    loan: String,
+    mut events: Vec<LoanEvent>,
+    floor_prices: &HashMap<String, u64>,
+) -> Option<NftLoan> {
+    events.sort_by_key(|event| event.block_time);
+    let borrow = events
+        .iter()
+        .find(|event| event.kind == Some(LoanEventKind::Borrow))?;
+    let started_at = borrow.block_time;
+
+    let principal = events.iter().find_map(|event| event.principal);
+    let hello_moon_collection_id: Option<String> = events
+        .iter()
+        .find_map(|event| event.hello_moon_collection_id.clone());
+    let ltv = hello_moon_collection_id
+        .as_ref()
+        .and_then(|id| floor_prices.get(id))
+        .filter(|floor_price| **floor_price > 0)
+        .and_then(|floor_price| Some(principal? as f64 / *floor_price as f64));
+
+    let closing = events.iter().rev().find_map(|event| {
+        let outcome = match event.kind? {
+            LoanEventKind::Repay => LoanOutcome::Repaid,
+            LoanEventKind::Refinance => LoanOutcome::Refinanced,
+            LoanEventKind::Default => LoanOutcome::Defaulted,
+            _ => return None,
+        };
+        Some((outcome, event.block_time))
+    });
+    let (outcome, closed_at) = closing.unwrap_or((LoanOutcome::Active, None));
+
+    Some(NftLoan {
+        protocol: events.iter().find_map(|event| event.protocol),
+        loan,
+        collateral_mint: events
+            .iter()
+            .find_map(|event| event.collateral_mint.clone()),
+        hello_moon_collection_id,
+        lender: events.iter().find_map(|event| event.lender.clone()),
+        borrower: events.iter().find_map(|event| event.borrower.clone()),
+        principal,
+        apr: events.iter().find_map(|event| event.apr),
+        duration: events.iter().find_map(|event| event.duration),
+        ltv,
+        started_at,
+        closed_at,
+        outcome,
+        events,
+    })
+}
+
+/// Rebuild the loans of `events` and order them into one timeline per collateral mint.
+///
+/// `floor_prices` are in lamports, keyed by `helloMoonCollectionId`, loans of collections
+/// without a floor price have no LTV. Events without a loan account are ignored, loans without
+/// a collateral mint are left out. The timelines are sorted by collateral mint.
+pub fn build_loan_timelines(
+    events: impl IntoIterator<Item = LoanEvent>,
+    floor_prices: &HashMap<String, u64>,
+) -> Vec<CollateralTimeline> {
+    let mut loans: HashMap<(Option<LendingProtocol>, String), Vec<LoanEvent>> = HashMap::new();
+    for event in events {
+        if let Some(loan) = event.loan.clone() {
+            loans.entry((event.protocol, loan)).or_default().push(event);
+        }
+    }
+
+    let mut timelines: BTreeMap<String, Vec<NftLoan>> = BTreeMap::new();
+    for ((_, loan), events) in loans {
+        let Some(loan) = build_loan(loan, events, floor_prices) else {
+            continue;
+        };
+        if let Some(collateral_mint) = loan.collateral_mint.clone() {
+            timelines.entry(collateral_mint).or_default().push(loan);
+        }
+    }
+    timelines
+        .into_iter()
+        .map(|(collateral_mint, mut loans)| {
+            loans.sort_by(|a, b| (a.started_at, &a.loan).cmp(&(b.started_at, &b.loan)));
+            CollateralTimeline {
+                collateral_mint,
+                loans,
+            }
+        })
+        .collect()
+}
+
+/// Fetch the loan events of a collection on every supported protocol and rebuild its loans.
+///
+/// `floor_price` is in lamports, e.g. the floor price of the collection stats summary.
+pub async fn collection_nft_loans(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    floor_price: Option<u64>,
+) -> anyhow::Result<Vec<CollateralTimeline>> {
+    let mut events: Vec<LoanEvent> = vec![];
+
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = CitrusLoanEventsRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = citrus_loan_events(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    events.extend(rows.iter().map(LoanEvent::from));
+
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = FraktLoanEventsRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = frakt_loan_events(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    events.extend(rows.iter().map(LoanEvent::from));
+
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = FraktBondsLoanEventsRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = frakt_bonds_loan_events(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    events.extend(rows.iter().map(LoanEvent::from));
+
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = SharkyLoanEventsRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = sharky_loan_events(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    events.extend(rows.iter().map(LoanEvent::from));
+
+    let floor_prices = floor_price
+        .map(|floor_price| (hello_moon_collection_id.to_string(), floor_price))
+        .into_iter()
+        .collect();
+    Ok(build_loan_timelines(events, &floor_prices))
+}
+
+#[test]
+fn test_build_loan_timelines() {
+    let citrus = |action: CitrusLoanAction, block_time: usize| CitrusLoanEvent {
+        action_type: Some(action),
+        block_time: Some(block_time),
+        loan_account: Some("citrus-loan".to_string()),
+        lender: Some("lender".to_string()),
+        borrower: (action != CitrusLoanAction::Offer).then(|| "borrower".to_string()),
+        collateral_mint: Some("mint".to_string()),
+        hello_moon_collection_id: Some("collection".to_string()),
+        loan_amount: Some(1_000_000_000),
+        apr_bps: Some(10_000),
+        duration: Some(86400 * 7),
+        ..Default::default()
+    };
+    let sharky = |action: SharkyLoanAction, loan: &str, block_time: usize| SharkyLoanEvent {
+        action_type: Some(action),
+        block_time: Some(block_time),
+        loan: Some(loan.to_string()),
+        collateral_mint: Some("mint".to_string()),
+        hello_moon_collection_id: Some("collection".to_string()),
+        principal_lamports: Some(2_000_000_000),
+        apy: Some(100.0),
+        duration_seconds: Some(86400 * 14),
+        ..Default::default()
+    };
+    let frakt = FraktLoanEvent {
+        action_type: Some(FraktLoanAction::ApproveLoan),
+        block_time: Some(5_000_000),
+        loan: Some("frakt-loan".to_string()),
+        nft_mint: Some("other-mint".to_string()),
+        hello_moon_collection_id: Some("collection".to_string()),
+        loan_value: Some(1_000_000_000),
+        repay_value: Some(1_010_000_000),
+        return_period_days: Some(7),
+        ..Default::default()
+    };
+
+    let mut events: Vec<LoanEvent> = vec![
+        LoanEvent::from(&citrus(CitrusLoanAction::Repay, 2_000)),
+        LoanEvent::from(&citrus(CitrusLoanAction::Offer, 0)),
+        LoanEvent::from(&citrus(CitrusLoanAction::Borrow, 1_000)),
+        LoanEvent::from(&sharky(SharkyLoanAction::TakeLoan, "sharky-loan", 3_000)),
+        LoanEvent::from(&sharky(
+            SharkyLoanAction::ForecloseLoan,
+            "sharky-loan",
+            4_000_000,
+        )),
+        LoanEvent::from(&sharky(SharkyLoanAction::OfferLoan, "untaken", 3_000)),
+        LoanEvent::from(&frakt),
+    ];
+    events.push(LoanEvent {
+        loan: None,
+        ..events[0].clone()
+    });
+    let floor_prices = HashMap::from([("collection".to_string(), 4_000_000_000)]);
+
+    let timelines = build_loan_timelines(events, &floor_prices);
+
+    assert_eq!(timelines.len(), 2);
+    let timeline = &timelines[0];
+    assert_eq!(timeline.collateral_mint, "mint");
+    assert_eq!(timeline.loans.len(), 2);
+    assert_eq!(timeline.defaults(), 1);
+
+    let citrus_loan = &timeline.loans[0];
+    assert_eq!(citrus_loan.protocol, Some(LendingProtocol::Citrus));
+    assert_eq!(citrus_loan.events.len(), 3);
+    assert_eq!(citrus_loan.borrower.as_deref(), Some("borrower"));
+    assert_eq!(citrus_loan.outcome, LoanOutcome::Repaid);
+    assert_eq!(citrus_loan.started_at, Some(1_000));
+    assert_eq!(citrus_loan.closed_at, Some(2_000));
+    assert_eq!(citrus_loan.apr, Some(100.0));
+    assert_eq!(citrus_loan.ltv, Some(0.25));
+
+    let sharky_loan = &timeline.loans[1];
+    assert_eq!(sharky_loan.outcome, LoanOutcome::Defaulted);
+    assert_eq!(sharky_loan.due_at(), Some(3_000 + 86400 * 14));
+    assert_eq!(sharky_loan.ltv, Some(0.5));
+
+    let frakt_loan = &timelines[1].loans[0];
+    assert_eq!(frakt_loan.outcome, LoanOutcome::Active);
+    assert!(frakt_loan.is_overdue(5_000_000 + 86400 * 8));
+    let apr = frakt_loan.apr.unwrap();
+    assert!((apr - 1.0 * 365.0 / 7.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_collection_nft_loans() {
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let timelines = collection_nft_loans(&api_key, "040de757c0d2b75dcee999ddd47689c4", None)
+        .await
+        .unwrap();
+
+    println!("{:#?}", timelines.first());
+}
@@ -0,0 +1,285 @@
+//! # Rarity
+//!
+//! Trait distribution and rarity ranks of the mints of a collection.
+//!
+//! The attributes of every mint are taken from its off-chain metadata. A mint without a value
+//! for a trait type that other mints of the collection have gets the value `None` for it,
+//! so missing traits are counted as rare as they are.
+//!
+//! Two rarity scores are computed per mint, both ranked with 1 as the rarest:
+//!
+//! - statistical rarity: the product of the frequencies of the mint's trait values, lower is rarer
+//! - information content: the sum of `-log2(frequency)` of the mint's trait values, higher is rarer
+use std::collections::BTreeMap;
+
+use crate::dimension_facts::nft::collection_mint_mapping::{
+    collection_mint_mapping, CollectionMintMappingRequest,
+};
+use crate::dimension_facts::nft::metaplex_metadata::{metaplex_metadata, MetaplexMetadataRequest};
+use crate::dimension_facts::nft::off_chain_metadata::{
+    resolve_off_chain_metadata, MetadataFetcher, OffChainMetadata,
+};
+use crate::fetch_all_pages;
+use serde::{Deserialize, Serialize};
+
+/// Trait value of a mint missing a trait type.
+pub const NONE_TRAIT_VALUE: &str = "None";
+
+/// The attributes of a mint, keyed by trait type.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct MintTraits {
+    pub nft_mint: String,
+    pub traits: BTreeMap<String, String>,
+}
+
+impl MintTraits {
+    /// Attributes without a trait type or value are ignored.
+    pub fn from_off_chain(nft_mint: impl Into<String>, metadata: &OffChainMetadata) -> Self {
+        let traits = metadata
+            .attributes
+            .iter()
+            .filter_map(|attribute| {
+                Some((attribute.trait_type.clone()?, attribute.value_string()?))
+            })
+            .collect();
+        Self {
+            nft_mint: nft_mint.into(),
+            traits,
+        }
+    }
+}
+
+/// Number of mints per trait type and value.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct TraitDistribution {
+    /// Number of mints in the collection
+    pub total: usize,
+    /// trait type => trait value => number of mints
+    pub traits: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl TraitDistribution {
+    pub fn new(mints: &[MintTraits]) -> Self {
+        let mut traits: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+        for mint in mints {
+            for (trait_type, value) in &mint.traits {
+                *traits
+                    .entry(trait_type.clone())
+                    .or_default()
+                    .entry(value.clone())
+                    .or_default() += 1;
+            }
+        }
+        for values in traits.values_mut() {
+            let with_value: usize = values.values().sum();
+            if with_value < mints.len() {
+                *values.entry(NONE_TRAIT_VALUE.to_string()).or_default() +=
+                    mints.len() - with_value;
+            }
+        }
+        Self {
+            total: mints.len(),
+            traits,
+        }
+    }
+
+    /// Share of the mints having `value` for `trait_type`, between 0 and 1.
+    pub fn frequency(&self, trait_type: &str, value: &str) -> f64 {
+        let count = self
+            .traits
+            .get(trait_type)
+            .and_then(|values| values.get(value))
+            .copied()
+            .unwrap_or_default();
+        if self.total == 0 {
+            0.0
+        } else {
+            count as f64 / self.total as f64
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct MintRarity {
+    pub nft_mint: String,
+    /// Product of the trait value frequencies, lower is rarer
+    pub statistical_rarity: f64,
+    /// Sum of `-log2(frequency)` of the trait values, higher is rarer
+    pub information_content: f64,
+    /// 1 is the rarest, mints with the same score share a rank
+    pub statistical_rank: usize,
+    /// 1 is the rarest, mints with the same score share a rank
+    pub information_content_rank: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionRarity {
+    pub distribution: TraitDistribution,
+    /// Sorted by information content rank
+    pub mints: Vec<MintRarity>,
+}
+
+/// Build the trait distribution of `mints` and rank them by rarity.
+pub fn compute_rarity(mints: &[MintTraits]) -> CollectionRarity {
+    let distribution = TraitDistribution::new(mints);
+
+    let mut rarities: Vec<MintRarity> = mints
+        .iter()
+        .map(|mint| {
+            let frequencies = distribution.traits.keys().map(|trait_type| {
+                let value = mint
+                    .traits
+                    .get(trait_type)
+                    .map_or(NONE_TRAIT_VALUE, String::as_str);
+                distribution.frequency(trait_type, value)
+            });
+            let (statistical_rarity, information_content) = frequencies
+                .fold((1.0, 0.0), |(product, sum), frequency| {
+                    (product * frequency, sum - frequency.log2())
+                });
+            MintRarity {
+                nft_mint: mint.nft_mint.clone(),
+                statistical_rarity,
+                information_content,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    rank(
+        &mut rarities,
+        |a, b| a.statistical_rarity.total_cmp(&b.statistical_rarity),
+        |rarity, rank| rarity.statistical_rank = rank,
+    );
+    rank(
+        &mut rarities,
+        |a, b| b.information_content.total_cmp(&a.information_content),
+        |rarity, rank| rarity.information_content_rank = rank,
+    );
+
+    CollectionRarity {
+        distribution,
+        mints: rarities,
+    }
+}
+
+/// Sort `rarities` rarest first and assign competition ranks (1, 2, 2, 4).
+fn rank(
+    rarities: &mut [MintRarity],
+    rarest_first: impl Fn(&MintRarity, &MintRarity) -> std::cmp::Ordering,
+    set_rank: impl Fn(&mut MintRarity, usize),
+) {
+    rarities.sort_by(|a, b| rarest_first(a, b).then_with(|| a.nft_mint.cmp(&b.nft_mint)));
+    let mut rank = 0;
+    for index in 0..rarities.len() {
+        if index == 0 || rarest_first(&rarities[index - 1], &rarities[index]).is_ne() {
+            rank = index + 1;
+        }
+        set_rank(&mut rarities[index], rank);
+    }
+}
+
+/// Fetch every mint of a collection and its off-chain attributes, and rank them by rarity.
+///
+/// Mints whose off-chain metadata can not be resolved are left out of the distribution.
+pub async fn collection_rarity<F: MetadataFetcher>(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    fetcher: &F,
+) -> anyhow::Result<CollectionRarity> {
+    let mappings = fetch_all_pages(|pagination_token| {
+        let request = CollectionMintMappingRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = collection_mint_mapping(api_key, Some(request)).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    let nft_mints = mappings.into_iter().filter_map(|mapping| mapping.nft_mint);
+
+    let mut mints = vec![];
+    for nft_mint in nft_mints {
+        let request = MetaplexMetadataRequest {
+            nft_mint: nft_mint.clone(),
+            ..Default::default()
+        };
+        let response = metaplex_metadata(api_key, Some(request)).await?;
+        let Some(metadata) = response.data.unwrap_or_default().into_iter().next() else {
+            continue;
+        };
+        match resolve_off_chain_metadata(fetcher, &metadata).await {
+            Ok(Some(off_chain)) => mints.push(MintTraits::from_off_chain(nft_mint, &off_chain)),
+            Ok(None) => {}
+            Err(e) => log::warn!(
+                "failed to resolve off-chain metadata of {}: {}",
+                nft_mint,
+                e
+            ),
+        }
+    }
+
+    Ok(compute_rarity(&mints))
+}
+
+#[test]
+fn test_compute_rarity() {
+    let mint = |nft_mint: &str, traits: &[(&str, &str)]| MintTraits {
+        nft_mint: nft_mint.to_string(),
+        traits: traits
+            .iter()
+            .map(|(trait_type, value)| (trait_type.to_string(), value.to_string()))
+            .collect(),
+    };
+    let mints = vec![
+        mint("a", &[("Fur", "Brown"), ("Hat", "Cap")]),
+        mint("b", &[("Fur", "Brown"), ("Hat", "Cap")]),
+        mint("c", &[("Fur", "Brown")]),
+        mint("d", &[("Fur", "Gold"), ("Hat", "Cap")]),
+    ];
+
+    let rarity = compute_rarity(&mints);
+
+    assert_eq!(rarity.distribution.total, 4);
+    assert_eq!(rarity.distribution.traits["Fur"]["Brown"], 3);
+    assert_eq!(rarity.distribution.traits["Hat"][NONE_TRAIT_VALUE], 1);
+    assert_eq!(rarity.distribution.frequency("Hat", "Cap"), 0.75);
+
+    let ranks: Vec<(&str, usize, usize)> = rarity
+        .mints
+        .iter()
+        .map(|mint| {
+            (
+                mint.nft_mint.as_str(),
+                mint.information_content_rank,
+                mint.statistical_rank,
+            )
+        })
+        .collect();
+    assert_eq!(
+        ranks,
+        vec![("c", 1, 1), ("d", 1, 1), ("a", 3, 3), ("b", 3, 3)]
+    );
+    assert_eq!(rarity.mints[0].statistical_rarity, 0.75 * 0.25);
+    assert_eq!(rarity.mints[0].information_content, 2.0 - 0.75f64.log2());
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_collection_rarity() {
+    use crate::dimension_facts::nft::off_chain_metadata::HttpFetcher;
+
+    let api_key = dotenv::var("api_keys").unwrap();
+    let rarity = collection_rarity(
+        &api_key,
+        "040de757c0d2b75dcee999ddd47689c4",
+        &HttpFetcher::default(),
+    )
+    .await
+    .unwrap();
+
+    println!("{:#?}", rarity.mints.iter().take(10).collect::<Vec<_>>());
+}
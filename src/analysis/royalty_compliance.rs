@@ -0,0 +1,371 @@
+//! # Royalty Compliance
+//!
+//! Joins Secondary Sales with the NFT Royalty and Metaplex Metadata endpoints to compare
+//! the royalty the creators should have received (`price * sellerFeeBasisPoints / 10_000`)
+//! with the royalty that was actually paid.
+//!
+//! The result is broken down per sale, per marketplace and per creator wallet,
+//! where the royalties of a sale are split across the verified and unverified creators by their `share`.
+//! Sales of a mint without metadata have an unknown royalty: they are counted apart and left out
+//! of the expected and paid royalties.
+use std::collections::{BTreeMap, HashMap};
+
+use crate::dimension_facts::nft::metaplex_metadata::{
+    metaplex_metadata, MetaplexMetadata, MetaplexMetadataRequest,
+};
+use crate::dimension_facts::nft::nft_royalty::{nft_royalty, NftRoyalty, NftRoyaltyRequest};
+use crate::dimension_facts::nft::secondary_sales::{
+    scondary_sales, SecondarySales, SecondarySalesRequest,
+};
+use crate::fetch_all_pages;
+use crate::parse_sol;
+use crate::summary::common::BlockTimeRange;
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+
+/// Royalty expected and paid for a single secondary sale. All amounts are in lamports.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SaleRoyalty {
+    pub transaction_id: String,
+    pub nft_mint: String,
+    pub marketplace: String,
+    pub block_time: usize,
+    pub price: u64,
+    pub seller_fee_basis_points: u64,
+    pub expected_royalty: u64,
+    /// `None` when no royalty row was found for the sale.
+    pub royalty_paid: Option<u64>,
+}
+
+impl SaleRoyalty {
+    /// The part of the expected royalty that was not paid.
+    pub fn shortfall(&self) -> u64 {
+        self.expected_royalty
+            .saturating_sub(self.royalty_paid.unwrap_or_default())
+    }
+}
+
+/// Royalty totals of every sale that happened on a marketplace. All amounts are in lamports.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct MarketplaceRoyalty {
+    pub marketplace: String,
+    pub sales: usize,
+    /// Number of sales for which no royalty row was found, they count as unpaid.
+    pub sales_without_royalty_data: usize,
+    /// Number of sales of a mint without metadata, whose royalty is unknown.
+    pub sales_with_unknown_royalty: usize,
+    pub volume: u64,
+    /// Part of `volume` made by the sales with an unknown royalty.
+    pub unknown_royalty_volume: u64,
+    pub expected_royalty: u64,
+    pub royalty_paid: u64,
+}
+
+impl MarketplaceRoyalty {
+    /// Ratio of paid to expected royalties, `1.0` means full royalty enforcement.
+    pub fn compliance(&self) -> Option<f64> {
+        (self.expected_royalty > 0).then(|| self.royalty_paid as f64 / self.expected_royalty as f64)
+    }
+}
+
+/// The share of a sale's royalty attributed to one creator.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CreatorLedgerEntry {
+    pub transaction_id: String,
+    pub nft_mint: String,
+    pub marketplace: String,
+    pub block_time: usize,
+    pub expected_royalty: u64,
+    pub royalty_paid: u64,
+}
+
+/// Royalty ledger of a creator wallet. All amounts are in lamports.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CreatorRoyaltyLedger {
+    pub address: String,
+    pub expected_royalty: u64,
+    pub royalty_paid: u64,
+    pub entries: Vec<CreatorLedgerEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct RoyaltyComplianceReport {
+    pub sales: Vec<SaleRoyalty>,
+    /// Sorted by marketplace name
+    pub marketplaces: Vec<MarketplaceRoyalty>,
+    /// Sorted by creator address
+    pub creators: Vec<CreatorRoyaltyLedger>,
+    pub expected_royalty: u64,
+    pub royalty_paid: u64,
+    /// Number of sales of a mint without metadata, left out of `sales` and the royalty totals.
+    pub sales_with_unknown_royalty: usize,
+}
+
+/// `amount * numerator / denominator` without overflowing, saturated to `u64::MAX`.
+fn mul_div(amount: u64, numerator: u64, denominator: u64) -> u64 {
+    let result = amount as u128 * numerator as u128 / denominator as u128;
+    result.try_into().unwrap_or(u64::MAX)
+}
+
+/// Build the royalty compliance report of already fetched rows.
+///
+/// Sales without a `transactionId`, `nftMint` or a parsable `price` are skipped. Sales of mints
+/// missing from `metadata` are counted as having an unknown royalty. `royalties` are matched to
+/// sales by `transactionId`.
+pub fn analyze_royalty_compliance(
+    sales: &[SecondarySales],
+    royalties: &[NftRoyalty],
+    metadata: &[MetaplexMetadata],
+) -> RoyaltyComplianceReport {
+    let paid: HashMap<&str, u64> = royalties
+        .iter()
+        .filter_map(|royalty| {
+            Some((
+                royalty.transaction_id.as_deref()?,
+                royalty.royalty_paid? as u64,
+            ))
+        })
+        .collect();
+    let metadata: HashMap<&str, &MetaplexMetadata> = metadata
+        .iter()
+        .filter_map(|metadata| Some((metadata.nft_mint.as_deref()?, metadata)))
+        .collect();
+
+    let mut report = RoyaltyComplianceReport::default();
+    let mut marketplaces: BTreeMap<String, MarketplaceRoyalty> = BTreeMap::new();
+    let mut creators: BTreeMap<String, CreatorRoyaltyLedger> = BTreeMap::new();
+
+    for sale in sales {
+        let (Some(transaction_id), Some(nft_mint), Some(price)) = (
+            sale.transaction_id.as_deref(),
+            sale.nft_mint.as_deref(),
+            sale.price.as_deref().and_then(parse_sol),
+        ) else {
+            continue;
+        };
+        let marketplace_name = sale.marketplace.clone().unwrap_or_default();
+        let marketplace = marketplaces
+            .entry(marketplace_name.clone())
+            .or_insert_with(|| MarketplaceRoyalty {
+                marketplace: marketplace_name.clone(),
+                ..Default::default()
+            });
+        marketplace.sales += 1;
+        marketplace.volume += price;
+
+        let Some(json) = metadata
+            .get(nft_mint)
+            .and_then(|metadata| metadata.nft_metadata_json.as_ref())
+        else {
+            marketplace.sales_with_unknown_royalty += 1;
+            marketplace.unknown_royalty_volume += price;
+            report.sales_with_unknown_royalty += 1;
+            continue;
+        };
+        let seller_fee_basis_points = json.seller_fee_basis_points.unwrap_or_default() as u64;
+        let sale_royalty = SaleRoyalty {
+            transaction_id: transaction_id.to_string(),
+            nft_mint: nft_mint.to_string(),
+            marketplace: marketplace_name,
+            block_time: sale
+                .block_time
+                .as_deref()
+                .and_then(|block_time| block_time.parse().ok())
+                .unwrap_or_default(),
+            price,
+            seller_fee_basis_points,
+            expected_royalty: mul_div(price, seller_fee_basis_points, 10_000),
+            royalty_paid: paid.get(transaction_id).copied(),
+        };
+        let royalty_paid = sale_royalty.royalty_paid.unwrap_or_default();
+
+        marketplace.sales_without_royalty_data += usize::from(sale_royalty.royalty_paid.is_none());
+        marketplace.expected_royalty += sale_royalty.expected_royalty;
+        marketplace.royalty_paid += royalty_paid;
+
+        for creator in json.creators.iter().flatten() {
+            let (Some(address), Some(share)) = (creator.address.as_ref(), creator.share) else {
+                continue;
+            };
+            let entry = CreatorLedgerEntry {
+                transaction_id: sale_royalty.transaction_id.clone(),
+                nft_mint: sale_royalty.nft_mint.clone(),
+                marketplace: sale_royalty.marketplace.clone(),
+                block_time: sale_royalty.block_time,
+                expected_royalty: mul_div(sale_royalty.expected_royalty, share as u64, 100),
+                royalty_paid: mul_div(royalty_paid, share as u64, 100),
+            };
+            let ledger = creators
+                .entry(address.clone())
+                .or_insert_with(|| CreatorRoyaltyLedger {
+                    address: address.clone(),
+                    ..Default::default()
+                });
+            ledger.expected_royalty += entry.expected_royalty;
+            ledger.royalty_paid += entry.royalty_paid;
+            ledger.entries.push(entry);
+        }
+
+        report.expected_royalty += sale_royalty.expected_royalty;
+        report.royalty_paid += royalty_paid;
+        report.sales.push(sale_royalty);
+    }
+
+    report.marketplaces = marketplaces.into_values().collect();
+    report.creators = creators.into_values().collect();
+    report
+}
+
+/// Number of metadata requests in flight at once.
+const METADATA_CONCURRENCY: usize = 8;
+
+/// Fetch every secondary sale and royalty row of a collection with a block time in
+/// `start_time..end_time` (epoch seconds) together with the metadata of the sold mints,
+/// and build the royalty compliance report.
+///
+/// Metadata that fails to be fetched is logged and treated as missing: the sales of the mint are
+/// counted as having an unknown royalty instead of failing the report.
+pub async fn royalty_compliance(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    start_time: usize,
+    end_time: usize,
+) -> anyhow::Result<RoyaltyComplianceReport> {
+    // The api range includes its end, the rows at `end_time` are dropped afterwards.
+    let block_time = BlockTimeRange::between(start_time, end_time);
+    let in_window = |block_time: Option<usize>| {
+        block_time.is_some_and(|block_time| (start_time..end_time).contains(&block_time))
+    };
+
+    let mut sales = fetch_all_pages(|pagination_token| {
+        let request = SecondarySalesRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            block_time: Some(block_time.clone()),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = scondary_sales(api_key, Some(request)).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    sales.retain(|sale| {
+        in_window(
+            sale.block_time
+                .as_deref()
+                .and_then(|block_time| block_time.parse().ok()),
+        )
+    });
+
+    let mut royalties = fetch_all_pages(|pagination_token| {
+        let request = NftRoyaltyRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            block_time: Some(block_time.clone()),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = nft_royalty(api_key, Some(request)).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    royalties.retain(|royalty| in_window(royalty.block_time));
+
+    let mut mints: Vec<&str> = sales
+        .iter()
+        .filter_map(|sale| sale.nft_mint.as_deref())
+        .collect();
+    mints.sort_unstable();
+    mints.dedup();
+    let metadata: Vec<MetaplexMetadata> = stream::iter(mints)
+        .map(|nft_mint| async move {
+            let request = MetaplexMetadataRequest {
+                nft_mint: nft_mint.to_string(),
+                ..Default::default()
+            };
+            match metaplex_metadata(api_key, Some(request)).await {
+                Ok(response) => response.data.unwrap_or_default(),
+                Err(e) => {
+                    log::warn!("failed to fetch the metadata of {}: {}", nft_mint, e);
+                    vec![]
+                }
+            }
+        })
+        .buffer_unordered(METADATA_CONCURRENCY)
+        .flat_map(stream::iter)
+        .collect()
+        .await;
+
+    Ok(analyze_royalty_compliance(&sales, &royalties, &metadata))
+}
+
+#[test]
+fn test_analyze_royalty_compliance() {
+    use crate::dimension_facts::nft::metaplex_metadata::MetaplexMetadataResponse;
+    use crate::dimension_facts::nft::nft_royalty::NftRoyaltyResponse;
+    use crate::dimension_facts::nft::secondary_sales::SecondarySalesResponse;
+    use crate::fixture;
+
+    let sales: SecondarySalesResponse = fixture("royalty_compliance/secondary_sales");
+    let royalties: NftRoyaltyResponse = fixture("royalty_compliance/nft_royalty");
+    let metadata: MetaplexMetadataResponse = fixture("royalty_compliance/metaplex_metadata");
+    let (sales, royalties, metadata) = (
+        sales.data.unwrap(),
+        royalties.data.unwrap(),
+        metadata.data.unwrap(),
+    );
+
+    let report = analyze_royalty_compliance(&sales, &royalties, &metadata);
+
+    assert_eq!(report.sales.len(), 3);
+    assert_eq!(report.sales[1].royalty_paid, None);
+    assert_eq!(report.sales[1].shortfall(), 50_000_000);
+    assert_eq!(report.expected_royalty, 225_000_000);
+    assert_eq!(report.royalty_paid, 100_000_000);
+    assert_eq!(report.sales_with_unknown_royalty, 1);
+
+    assert_eq!(report.marketplaces.len(), 2);
+    let magic_eden = &report.marketplaces[0];
+    assert_eq!(magic_eden.marketplace, "ME_V2");
+    assert_eq!(magic_eden.sales, 2);
+    assert_eq!(magic_eden.sales_without_royalty_data, 1);
+    assert_eq!(magic_eden.volume, 3_000_000_000);
+    assert_eq!(magic_eden.compliance(), Some(100_000_000. / 150_000_000.));
+    assert_eq!(report.marketplaces[1].compliance(), Some(0.0));
+    assert_eq!(report.marketplaces[1].sales, 2);
+    assert_eq!(report.marketplaces[1].sales_with_unknown_royalty, 1);
+    assert_eq!(report.marketplaces[1].volume, 4_500_000_000);
+    assert_eq!(report.marketplaces[1].unknown_royalty_volume, 3_000_000_000);
+
+    assert_eq!(report.creators.len(), 2);
+    assert_eq!(
+        report.creators[0].address,
+        "3pMvTLUA9NzZQd4gi725p89mvND1wRNQM3C8XEv1hTdA"
+    );
+    assert_eq!(report.creators[0].expected_royalty, 0);
+    assert_eq!(report.creators[1].expected_royalty, 225_000_000);
+    assert_eq!(report.creators[1].royalty_paid, 100_000_000);
+    assert_eq!(report.creators[1].entries.len(), 3);
+
+    assert_eq!(mul_div(u64::MAX, 500, 10_000), u64::MAX / 20);
+    assert_eq!(mul_div(u64::MAX, 20_000, 10_000), u64::MAX);
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_royalty_compliance() {
+    let api_key = dotenv::var("api_keys").unwrap();
+    let end_time = 1673831466;
+    let report = royalty_compliance(
+        &api_key,
+        "040de757c0d2b75dcee999ddd47689c4",
+        end_time - 86400 * 7,
+        end_time,
+    )
+    .await
+    .unwrap();
+
+    println!("{:#?}", report.marketplaces);
+}
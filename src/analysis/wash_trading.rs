@@ -0,0 +1,355 @@
+//! # Wash Trading
+//!
+//! Flags suspicious secondary sales and scores each collection by the share of its volume that
+//! was flagged, from 0 to 100, the scale of Hello Moon's
+//! [washtrading index](crate::summary::nft_summary::collection_washtrading_index).
+//!
+//! A sale can raise several signals:
+//!
+//! - self trade: the buyer is the seller
+//! - round trip: the buyer sold an NFT of the same collection back to the seller, or bought one
+//!   from them, within [`WashTradingConfig::round_trip_window`]
+//! - loop: the NFT comes back to a wallet that sold it within [`WashTradingConfig::loop_window`],
+//!   every sale of the loop is flagged
+//! - price outlier: the price is more than [`WashTradingConfig::price_outlier_ratio`] times above
+//!   or below the floor price
+use std::collections::{BTreeMap, HashMap};
+
+use crate::dimension_facts::nft::secondary_sales::{
+    scondary_sales, SecondarySales, SecondarySalesRequest,
+};
+use crate::fetch_all_pages;
+use crate::parse_sol;
+use crate::summary::common::BlockTimeRange;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct WashTradingConfig {
+    /// Seconds between two sales of opposite direction between the same wallets
+    pub round_trip_window: usize,
+    /// Seconds between the sale of an NFT by a wallet and its purchase back by the same wallet
+    pub loop_window: usize,
+    /// A price above `floor * ratio` or below `floor / ratio` is an outlier
+    pub price_outlier_ratio: f64,
+}
+
+impl Default for WashTradingConfig {
+    fn default() -> Self {
+        Self {
+            round_trip_window: 86400 * 7,
+            loop_window: 86400,
+            price_outlier_ratio: 3.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum WashTradingSignal {
+    SelfTrade,
+    RoundTrip,
+    Loop,
+    PriceOutlier,
+}
+
+/// A sale that raised at least one signal. `price` is in lamports.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct FlaggedSale {
+    pub transaction_id: String,
+    pub nft_mint: String,
+    pub seller: String,
+    pub buyer: String,
+    pub block_time: usize,
+    pub price: u64,
+    /// Sorted, without duplicates
+    pub signals: Vec<WashTradingSignal>,
+}
+
+/// Wash trading detected in the sales of a collection. Amounts are in lamports.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionWashTrading {
+    pub hello_moon_collection_id: String,
+    pub sales: usize,
+    pub volume: u64,
+    /// Sorted by block time
+    pub flagged: Vec<FlaggedSale>,
+    pub flagged_volume: u64,
+}
+
+impl CollectionWashTrading {
+    /// Share of the volume that was flagged, from 0 to 100.
+    pub fn score(&self) -> f64 {
+        if self.volume == 0 {
+            return 0.0;
+        }
+        self.flagged_volume as f64 * 100.0 / self.volume as f64
+    }
+
+    /// Number of flagged sales that raised `signal`.
+    pub fn count(&self, signal: WashTradingSignal) -> usize {
+        self.flagged
+            .iter()
+            .filter(|sale| sale.signals.contains(&signal))
+            .count()
+    }
+}
+
+struct Sale<'a> {
+    transaction_id: &'a str,
+    nft_mint: &'a str,
+    seller: &'a str,
+    buyer: &'a str,
+    block_time: usize,
+    price: u64,
+}
+
+/// Run the detector on already fetched sales, grouped by `helloMoonCollectionId`.
+///
+/// `floor_prices` are in lamports, keyed by `helloMoonCollectionId`. Collections without a floor
+/// price are not checked for price outliers. Sales missing a field the signals rely on are
+/// skipped. The result is sorted by collection.
+pub fn detect_wash_trading(
+    sales: &[SecondarySales],
+    floor_prices: &HashMap<String, u64>,
+    config: &WashTradingConfig,
+) -> Vec<CollectionWashTrading> {
+    let mut collections: BTreeMap<&str, Vec<Sale>> = BTreeMap::new();
+    for sale in sales {
+        let (
+            Some(hello_moon_collection_id),
+            Some(transaction_id),
+            Some(nft_mint),
+            Some(seller),
+            Some(buyer),
+            Some(block_time),
+            Some(price),
+        ) = (
+            sale.hello_moon_collection_id.as_deref(),
+            sale.transaction_id.as_deref(),
+            sale.nft_mint.as_deref(),
+            sale.seller.as_deref(),
+            sale.buyer.as_deref(),
+            sale.block_time
+                .as_deref()
+                .and_then(|block_time| block_time.parse().ok()),
+            sale.price.as_deref().and_then(parse_sol),
+        )
+        else {
+            continue;
+        };
+        collections
+            .entry(hello_moon_collection_id)
+            .or_default()
+            .push(Sale {
+                transaction_id,
+                nft_mint,
+                seller,
+                buyer,
+                block_time,
+                price,
+            });
+    }
+
+    collections
+        .into_iter()
+        .map(|(hello_moon_collection_id, mut sales)| {
+            sales.sort_by_key(|sale| sale.block_time);
+            let floor_price = floor_prices.get(hello_moon_collection_id).copied();
+            detect_collection(hello_moon_collection_id, &sales, floor_price, config)
+        })
+        .collect()
+}
+
+fn detect_collection(
+    hello_moon_collection_id: &str,
+    sales: &[Sale],
+    floor_price: Option<u64>,
+    config: &WashTradingConfig,
+) -> CollectionWashTrading {
+    let mut signals: Vec<Vec<WashTradingSignal>> = vec![vec![]; sales.len()];
+
+    let mut pairs: HashMap<(&str, &str), Vec<usize>> = HashMap::new();
+    let mut mints: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, sale) in sales.iter().enumerate() {
+        pairs.entry((sale.seller, sale.buyer)).or_default().push(i);
+        mints.entry(sale.nft_mint).or_default().push(i);
+    }
+
+    for (i, sale) in sales.iter().enumerate() {
+        if sale.seller == sale.buyer {
+            signals[i].push(WashTradingSignal::SelfTrade);
+            continue;
+        }
+        let round_trip = pairs
+            .get(&(sale.buyer, sale.seller))
+            .into_iter()
+            .flatten()
+            .any(|&j| sale.block_time.abs_diff(sales[j].block_time) <= config.round_trip_window);
+        if round_trip {
+            signals[i].push(WashTradingSignal::RoundTrip);
+        }
+        if let Some(floor_price) = floor_price.filter(|floor_price| *floor_price > 0) {
+            let ratio = sale.price as f64 / floor_price as f64;
+            if ratio > config.price_outlier_ratio || ratio < 1.0 / config.price_outlier_ratio {
+                signals[i].push(WashTradingSignal::PriceOutlier);
+            }
+        }
+    }
+
+    // Sales of a mint are in block time order, a purchase by a wallet that sold the mint
+    // earlier closes a loop made of every sale in between.
+    for indexes in mints.values() {
+        for (end, &i) in indexes.iter().enumerate() {
+            let sale = &sales[i];
+            let start = indexes[..end].iter().position(|&j| {
+                sales[j].seller == sale.buyer
+                    && sale.block_time - sales[j].block_time <= config.loop_window
+            });
+            if let Some(start) = start {
+                for &j in &indexes[start..=end] {
+                    signals[j].push(WashTradingSignal::Loop);
+                }
+            }
+        }
+    }
+
+    let mut report = CollectionWashTrading {
+        hello_moon_collection_id: hello_moon_collection_id.to_string(),
+        ..Default::default()
+    };
+    for (sale, mut signals) in sales.iter().zip(signals) {
+        report.sales += 1;
+        report.volume += sale.price;
+        if signals.is_empty() {
+            continue;
+        }
+        signals.sort_unstable();
+        signals.dedup();
+        report.flagged_volume += sale.price;
+        report.flagged.push(FlaggedSale {
+            transaction_id: sale.transaction_id.to_string(),
+            nft_mint: sale.nft_mint.to_string(),
+            seller: sale.seller.to_string(),
+            buyer: sale.buyer.to_string(),
+            block_time: sale.block_time,
+            price: sale.price,
+            signals,
+        });
+    }
+    report
+}
+
+/// Fetch every secondary sale of a collection with a block time in `start_time..end_time`
+/// (epoch seconds) and run the detector on them.
+///
+/// `floor_price` is in lamports, e.g. the floor price of the collection stats summary.
+pub async fn wash_trading(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    start_time: usize,
+    end_time: usize,
+    floor_price: Option<u64>,
+    config: &WashTradingConfig,
+) -> anyhow::Result<CollectionWashTrading> {
+    // The api range includes its end, the sales at `end_time` are dropped afterwards.
+    let block_time = BlockTimeRange::between(start_time, end_time);
+    let mut sales = fetch_all_pages(|pagination_token| {
+        let request = SecondarySalesRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            block_time: Some(block_time.clone()),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = scondary_sales(api_key, Some(request)).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    sales.retain(|sale| {
+        sale.block_time
+            .as_deref()
+            .and_then(|block_time| block_time.parse().ok())
+            .is_some_and(|block_time: usize| (start_time..end_time).contains(&block_time))
+    });
+
+    let floor_prices = floor_price
+        .map(|floor_price| (hello_moon_collection_id.to_string(), floor_price))
+        .into_iter()
+        .collect();
+    Ok(detect_wash_trading(&sales, &floor_prices, config)
+        .pop()
+        .unwrap_or_else(|| CollectionWashTrading {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            ..Default::default()
+        }))
+}
+
+#[test]
+fn test_detect_wash_trading() {
+    use crate::dimension_facts::nft::secondary_sales::SecondarySalesResponse;
+    use crate::fixture;
+    use WashTradingSignal::*;
+
+    // 0 and 1 are a round trip, 2 to 4 a loop, 5 a self trade and 6 an outlier at 10 SOL.
+    // 7 sells for "1": whole SOL prices are not lamports, it is not an outlier of a 1 SOL floor.
+    // 9 is a sale of another collection.
+    let response: SecondarySalesResponse = fixture("wash_trading/secondary_sales");
+    let sales = response.data.unwrap();
+    let transaction_id = |i: usize| sales[i].transaction_id.as_deref().unwrap();
+    let floor_prices = HashMap::from([(
+        "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        1_000_000_000,
+    )]);
+
+    let reports = detect_wash_trading(&sales, &floor_prices, &WashTradingConfig::default());
+
+    assert_eq!(reports.len(), 2);
+    let report = &reports[0];
+    assert_eq!(
+        report.hello_moon_collection_id,
+        "040de757c0d2b75dcee999ddd47689c4"
+    );
+    assert_eq!(report.sales, 9);
+    assert_eq!(report.volume, 18_000_000_000);
+    let signals: HashMap<&str, &[WashTradingSignal]> = report
+        .flagged
+        .iter()
+        .map(|sale| (sale.transaction_id.as_str(), sale.signals.as_slice()))
+        .collect();
+    assert_eq!(signals.len(), 7);
+    assert_eq!(signals[transaction_id(0)], [RoundTrip]);
+    assert_eq!(signals[transaction_id(1)], [RoundTrip]);
+    assert_eq!(signals[transaction_id(2)], [Loop]);
+    assert_eq!(signals[transaction_id(4)], [Loop]);
+    assert_eq!(signals[transaction_id(5)], [SelfTrade]);
+    assert_eq!(signals[transaction_id(6)], [PriceOutlier]);
+    assert!(!signals.contains_key(transaction_id(7)));
+    assert_eq!(report.count(Loop), 3);
+    assert_eq!(report.flagged_volume, 16_000_000_000);
+    assert_eq!(report.score(), 16.0 * 100.0 / 18.0);
+
+    assert_eq!(
+        reports[1].hello_moon_collection_id,
+        "fd3eb1ab3d0ab54ad4a8e2cf8ea3e2b5"
+    );
+    assert_eq!(reports[1].score(), 0.0);
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_wash_trading() {
+    let api_key = dotenv::var("api_keys").unwrap();
+    let end_time = 1673831466;
+    let report = wash_trading(
+        &api_key,
+        "040de757c0d2b75dcee999ddd47689c4",
+        end_time - 86400 * 7,
+        end_time,
+        None,
+        &WashTradingConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    println!("{} {:#?}", report.score(), report.flagged);
+}
@@ -5,8 +5,7 @@
 //! DeFi Swaps endpoint can be used to track and verify both large and small
 //! swap amounts that occur on the Solana blockchain. Hello Moon also provides key data fields such as the time of the swap, who made the swap, and the program and aggregator that was used.
 //!
-use std::default;
-
+use crate::hello_moon_pub_api::hello_moon::parsed_dexes::DexRegistry;
 use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, is_zero, limit_is_zero, page_is_zero};
 use serde::{Deserialize, Serialize};
@@ -40,13 +39,16 @@ pub struct DefiSwapsRequest {
     #[serde(rename = "blockTime")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub block_time: Option<BlockTimeType>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
 pub enum AggregatorName {
@@ -117,13 +119,52 @@ pub enum Operator {
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct DefiSwapsResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<IResponse>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct IResponse {
+    /// First signature in a transaction, which can be used to track and verify the transaction status across the complete ledger.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: Option<String>,
+    /// The block the swap was processed in
+    #[serde(rename = "blockId")]
+    pub block_id: Option<usize>,
+    /// Unix epoch time (in seconds) of the block
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<usize>,
+    /// The user that made the swap
+    #[serde(rename = "userAccount")]
+    pub user_account: Option<String>,
+    /// Mint of the token sent for the swap
+    #[serde(rename = "sourceMint")]
+    pub source_mint: Option<String>,
+    /// The amount of the source mint sent for the swap, in the source mint's native unit
+    #[serde(rename = "sourceAmount")]
+    pub source_amount: Option<u64>,
+    /// Mint of the token received from the swap
+    #[serde(rename = "destinationMint")]
+    pub destination_mint: Option<String>,
+    /// The amount of destination mints received from the swap, in the destination mint's native unit
+    #[serde(rename = "destinationAmount")]
+    pub destination_amount: Option<u64>,
+    /// The DEX program the swap was made on
+    #[serde(rename = "programId")]
+    pub program_id: Option<String>,
+    /// The aggregator that routed the swap — e.g. `Jupiter v4`
+    #[serde(rename = "aggregatorName")]
+    pub aggregator_name: Option<String>,
+}
+
+impl IResponse {
+    /// Name of the DEX the swap was made on.
+    pub fn dex_name<'a>(&self, registry: &'a DexRegistry) -> Option<&'a str> {
+        registry.name(self.program_id.as_deref()?)
+    }
+}
 
 pub async fn defi_swaps(
     request: Option<DefiSwapsRequest>,
@@ -141,6 +182,35 @@ pub async fn defi_swaps_return_json_value(
     core_call::<DefiSwapsRequest, serde_json::Value>(request, api, api_key).await
 }
 
+#[test]
+fn test_defi_swaps_dex_name() {
+    use crate::hello_moon_pub_api::hello_moon::parsed_dexes::ParsedDex;
+
+    let row: IResponse = serde_json::from_str(
+        r#"{
+            "transactionId": "tx",
+            "blockId": 172346553,
+            "blockTime": 1673831466,
+            "userAccount": "user",
+            "sourceMint": "So11111111111111111111111111111111111111112",
+            "sourceAmount": 1000000000,
+            "destinationMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "destinationAmount": 20000000,
+            "programId": "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
+            "aggregatorName": "Jupiter v4"
+        }"#,
+    )
+    .unwrap();
+    let registry = DexRegistry::new(vec![ParsedDex {
+        program_name: Some("Orca Whirlpool".to_string()),
+        program_id: Some("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string()),
+        ..Default::default()
+    }]);
+
+    assert_eq!(row.destination_amount, Some(20_000_000));
+    assert_eq!(row.dex_name(&registry), Some("Orca Whirlpool"));
+}
+
 #[tokio::test]
 async fn test_defi_swaps() {
     let request = DefiSwapsRequest::default();
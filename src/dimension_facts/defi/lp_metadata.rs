@@ -4,6 +4,7 @@
 //!
 //! Metadata on Liquidity Pools such as pool name and token names
 //!
+use crate::hello_moon_pub_api::hello_moon::parsed_dexes::DexRegistry;
 use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
 use serde::{Deserialize, Serialize};
@@ -85,6 +86,14 @@ pub struct IResponse {
     #[serde(rename = "tokenAccountB")]
     pub token_account_b: Option<String>,
 }
+
+impl IResponse {
+    /// Program id of the DEX the pool belongs to, resolved from its program name.
+    pub fn program_id<'a>(&self, registry: &'a DexRegistry) -> Option<&'a str> {
+        registry.program_id(self.program_name.as_deref()?)
+    }
+}
+
 pub async fn lp_metadata_return_json_value(
     request: Option<LpMetadataRequest>,
     api_key: &str,
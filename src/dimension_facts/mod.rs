@@ -1,3 +1,4 @@
 pub mod defi;
 pub mod nft;
+pub mod protocols;
 pub mod staking;
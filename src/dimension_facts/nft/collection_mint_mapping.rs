@@ -15,21 +15,21 @@ const COLLECTION_MINT_MAPPING_API: &str = "https://rest-api.hellomoon.io/v0/nft/
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct CollectionMintMappingResponse {
     /// array of objects
-    data: Option<Vec<CollectionMintMapping>>,
+    pub data: Option<Vec<CollectionMintMapping>>,
     /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
-struct CollectionMintMapping {
+pub struct CollectionMintMapping {
     /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
     #[serde(rename = "helloMoonCollectionId")]
-    hello_moon_collection_id: Option<String>,
+    pub hello_moon_collection_id: Option<String>,
     /// Mint address of nft per the spl token program.
     /// Each NFT has a unique mint address within the collection.
     #[serde(rename = "nftMint")]
-    nft_mint: Option<String>,
+    pub nft_mint: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
@@ -37,22 +37,22 @@ pub struct CollectionMintMappingRequest {
     /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
     #[serde(rename = "helloMoonCollectionId")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    hello_moon_collection_id: String,
+    pub hello_moon_collection_id: String,
     /// Mint address of nft per the spl token program.
     /// Each NFT has a unique mint address within the collection.
     #[serde(rename = "nftMint")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    nft_mint: String,
+    pub nft_mint: String,
     /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
     #[serde(skip_serializing_if = "page_is_zero")]
     /// The page number to return
-    page: usize,
+    pub page: usize,
     /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
 pub async fn collection_mint_mapping(
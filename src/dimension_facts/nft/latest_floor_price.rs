@@ -0,0 +1,82 @@
+//! # NFT Latest Floor Price
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/collection/floorprice`
+//!
+//! The NFT Latest Floor Price endpoint returns the current floor price of a collection,
+//! the lowest listing price across all supported marketplaces.
+//!
+//! helloMoonCollectionId is required to receive a successful query response.
+use crate::{core_call, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+const LATEST_FLOOR_PRICE_API_URL: &str =
+    "https://rest-api.hellomoon.io/v0/nft/collection/floorprice";
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct LatestFloorPriceResponse {
+    /// array of objects
+    pub data: Option<Vec<LatestFloorPrice>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct LatestFloorPrice {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// The lowest listing price of the collection, in lamports.
+    #[serde(rename = "floorPrice")]
+    pub floor_price: Option<u64>,
+    /// Numeric identifier of the block at which the floor price was computed
+    pub slot: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct LatestFloorPriceRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+pub async fn latest_floor_price(
+    api_key: &str,
+    request: Option<LatestFloorPriceRequest>,
+) -> anyhow::Result<LatestFloorPriceResponse> {
+    core_call::<LatestFloorPriceRequest, LatestFloorPriceResponse>(
+        request,
+        LATEST_FLOOR_PRICE_API_URL.to_string(),
+        api_key,
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_latest_floor_price() {
+    let request = LatestFloorPriceRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+    let left = latest_floor_price(&api_key, Some(request)).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: LatestFloorPriceResponse = serde_json::from_str(&r).unwrap();
+    assert_eq!(left, right);
+}
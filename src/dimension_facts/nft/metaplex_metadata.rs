@@ -9,10 +9,10 @@ const METAPLEX_METADATA_API_URL: &str = "https://rest-api.hellomoon.io/v0/nft/mi
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
 pub struct MetaplexMetadataResponse {
     /// array of objects
-    data: Option<Vec<MetaplexMetadata>>,
+    pub data: Option<Vec<MetaplexMetadata>>,
     /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
@@ -20,45 +20,179 @@ pub struct MetaplexMetadata {
     /// Mint address of nft per the spl token program.
     /// Each NFT has a unique mint address within the collection.
     #[serde(rename = "nftMint")]
-    nft_mint: Option<String>,
+    pub nft_mint: Option<String>,
     /// Public key of address holding NFT metadata
-    #[serde(rename = "nftMetadataAdress")]
-    nft_metadata_adress: Option<String>,
+    #[serde(rename = "nftMetadataAddress", alias = "nftMetadataAdress")]
+    pub nft_metadata_address: Option<String>,
     /// The NFT on chain metadata
     #[serde(rename = "nftMetadataJson")]
-    nft_metadata_json: Option<NftMetadataJson>,
+    pub nft_metadata_json: Option<NftMetadataJson>,
     /// The public key of the Collection NFT's Mint Account
     #[serde(rename = "nftCollectionMint")]
-    nft_collection_mint: Option<String>,
+    pub nft_collection_mint: Option<String>,
     /// Array of verified creators
     #[serde(rename = "nftVerifiedCreatorsArray")]
-    nft_verified_creators_array: Option<Vec<String>>,
+    pub nft_verified_creators_array: Option<Vec<String>>,
+    /// The Master Edition or Edition account of the NFT
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edition: Option<Edition>,
+}
+
+impl MetaplexMetadata {
+    /// The off-chain JSON uri of the NFT, with the null padding of the on-chain field removed.
+    pub fn uri(&self) -> Option<&str> {
+        let uri = self.nft_metadata_json.as_ref()?.uri.as_deref()?;
+        let uri = uri.trim_end_matches('\0').trim();
+        (!uri.is_empty()).then_some(uri)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
 pub struct NftMetadataJson {
+    /// The public key that is allowed to update the metadata account
+    #[serde(rename = "updateAuthority")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_authority: Option<String>,
     /// The on-chain name of the token, limited to 32 bytes
-    name: Option<String>,
+    pub name: Option<String>,
     /// The on-chain symbol of the token, limited to 10 bytes
-    symbol: Option<String>,
+    pub symbol: Option<String>,
     /// The URI of the token, limited to 200 bytes. This URI points to an off-chain JSON file that contains additional data following a certain standard.
-    uri: Option<String>,
+    pub uri: Option<String>,
     /// The royalties shared by the creators in basis points — i.e. 550 means 5.5%. Whilst this field is used by virtually all NFT marketplaces, it is not enforced by the Token Metadata program itself.
     #[serde(rename = "sellerFeeBasisPoints")]
-    seller_fee_basis_points: Option<usize>,
+    pub seller_fee_basis_points: Option<usize>,
     /// An array of creators and their share of the royalties. This array is limited to 5 creators.
     #[serde(skip_serializing_if = "Option::is_none")] // TODO, this I meet error.
-    creators: Option<Vec<Creator>>,
+    pub creators: Option<Vec<Creator>>,
+    /// A boolean indicating if the token has already been sold at least once.
+    /// Once flipped to true, it cannot be flipped back to false.
+    #[serde(rename = "primarySaleHappened")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_sale_happened: Option<bool>,
+    /// A boolean indicating if the metadata account can be updated by the update authority.
+    #[serde(rename = "isMutable")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_mutable: Option<bool>,
+    /// The bump of the edition PDA, used to find the Master Edition or Edition account of the NFT.
+    #[serde(rename = "editionNonce")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edition_nonce: Option<u8>,
+    /// The type of the token: NonFungible, FungibleAsset, Fungible, NonFungibleEdition or ProgrammableNonFungible.
+    #[serde(rename = "tokenStandard")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_standard: Option<TokenStandard>,
+    /// This field optionally links to the Mint address of another NFT that acts as a Collection NFT.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection: Option<Collection>,
+    /// This field can make NFTs usable, meaning you can load it with a certain amount of "uses" and use it until it has run out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uses: Option<Uses>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
 pub struct Creator {
     /// The publicKey of the creator
-    address: Option<String>,
+    pub address: Option<String>,
     /// A boolean indicating if the creator signed the NFT. It is important to check this field to ensure the authenticity of the creator.
-    verified: Option<bool>,
+    pub verified: Option<bool>,
     /// The share of the royalties that the creator gets. This is a number between 0 and 100. The sum of all shares must be 100.
-    share: Option<usize>,
+    pub share: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct Collection {
+    /// A boolean indicating if the owner of the Collection NFT signed this NFT. It is important to check this field to ensure the authenticity of the collection.
+    pub verified: Option<bool>,
+    /// The public key of the Collection NFT's Mint Account
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct Uses {
+    /// The way the uses are consumed: Burn, Multiple or Single
+    #[serde(rename = "useMethod")]
+    pub use_method: Option<UseMethod>,
+    /// The amount of uses left
+    pub remaining: Option<u64>,
+    /// The amount of uses the NFT was loaded with
+    pub total: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub enum UseMethod {
+    /// The NFT is burned after the last use
+    Burn,
+    /// The NFT can be used several times
+    Multiple,
+    /// The NFT can be used once
+    Single,
+}
+
+/// The type of a token per the Token Metadata program.
+///
+/// The api may return either the name or the on-chain index of the variant, both are accepted.
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+    ProgrammableNonFungible,
+    /// A standard not listed above, e.g. added to the program after this crate
+    Other,
+}
+
+impl<'de> Deserialize<'de> for TokenStandard {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Index(u8),
+            Name(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Index(0) => Ok(Self::NonFungible),
+            Repr::Index(1) => Ok(Self::FungibleAsset),
+            Repr::Index(2) => Ok(Self::Fungible),
+            Repr::Index(3) => Ok(Self::NonFungibleEdition),
+            Repr::Index(4) => Ok(Self::ProgrammableNonFungible),
+            Repr::Name(name) => match name.as_str() {
+                "NonFungible" => Ok(Self::NonFungible),
+                "FungibleAsset" => Ok(Self::FungibleAsset),
+                "Fungible" => Ok(Self::Fungible),
+                "NonFungibleEdition" => Ok(Self::NonFungibleEdition),
+                "ProgrammableNonFungible" => Ok(Self::ProgrammableNonFungible),
+                _ => Ok(Self::Other),
+            },
+            Repr::Index(_) => Ok(Self::Other),
+        }
+    }
+}
+
+/// Edition information of an NFT.
+///
+/// A Master Edition carries the `supply` and `maxSupply` of prints,
+/// a printed Edition carries its `parent` Master Edition and its `edition` number.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct Edition {
+    /// A boolean indicating if the NFT is a Master Edition
+    #[serde(rename = "isMasterEdition")]
+    pub is_master_edition: Option<bool>,
+    /// The number of prints of the Master Edition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supply: Option<u64>,
+    /// The maximum number of prints of the Master Edition, unlimited if absent
+    #[serde(rename = "maxSupply")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_supply: Option<u64>,
+    /// The Master Edition account of a printed Edition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// The number of a printed Edition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edition: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
@@ -67,21 +201,21 @@ pub struct MetaplexMetadataRequest {
     /// Each NFT has a unique mint address within the collection.
     #[serde(rename = "nftMint")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    nft_mint: String,
+    pub nft_mint: String,
     /// The public key of the Collection NFT's Mint Account
     #[serde(skip_serializing_if = "String::is_empty")]
     #[serde(rename = "nftCollectionMint")]
-    nft_collection_mint: String,
+    pub nft_collection_mint: String,
     /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
     /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
     /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
 pub async fn metaplex_metadata(
@@ -96,6 +230,44 @@ pub async fn metaplex_metadata(
     .await
 }
 
+#[test]
+fn test_metaplex_metadata_deserialize() {
+    let json = r#"{
+        "nftMint": "mint",
+        "nftMetadataAdress": "metadata",
+        "nftMetadataJson": {
+            "name": "Okay Bear #1",
+            "uri": "https://arweave.net/abc\u0000\u0000",
+            "sellerFeeBasisPoints": 500,
+            "tokenStandard": 4,
+            "collection": { "verified": true, "key": "collection" },
+            "uses": { "useMethod": "Single", "remaining": 1, "total": 1 }
+        },
+        "edition": { "isMasterEdition": true, "supply": 0, "maxSupply": 0 }
+    }"#;
+    let metadata: MetaplexMetadata = serde_json::from_str(json).unwrap();
+
+    assert_eq!(metadata.nft_metadata_address.as_deref(), Some("metadata"));
+    assert_eq!(metadata.uri(), Some("https://arweave.net/abc"));
+    let json = metadata.nft_metadata_json.as_ref().unwrap();
+    assert_eq!(
+        json.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    );
+    assert_eq!(
+        json.uses.as_ref().unwrap().use_method,
+        Some(UseMethod::Single)
+    );
+    assert_eq!(metadata.edition.unwrap().is_master_edition, Some(true));
+
+    for token_standard in ["5", r#""ProgrammableNonFungibleEdition""#] {
+        assert_eq!(
+            serde_json::from_str::<TokenStandard>(token_standard).unwrap(),
+            TokenStandard::Other
+        );
+    }
+}
+
 #[tokio::test]
 async fn test_metaplex_metadata() {
     let request = MetaplexMetadataRequest::default();
@@ -1,9 +1,14 @@
 pub mod collection_candlesticks;
 pub mod collection_mint_mapping;
 pub mod collection_name_mapping;
+pub mod latest_floor_price;
 pub mod listing_status;
 pub mod metaplex_metadata;
 pub mod mints_by_owner;
+pub mod nft_estimated_price;
 pub mod nft_listings;
+pub mod nft_ownership;
+pub mod nft_royalty;
+pub mod off_chain_metadata;
 pub mod primary_sales;
 pub mod secondary_sales;
@@ -0,0 +1,99 @@
+//! # NFT Estimated Price
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/estimated-price`
+//!
+//! The NFT Estimated Price endpoint returns the estimated value of a single NFT mint,
+//! derived from the recent sales of the mint's collection and the rarity of the mint.
+//!
+//! nftMint is required to receive a successful query response.
+use crate::{core_call, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+const NFT_ESTIMATED_PRICE_API_URL: &str = "https://rest-api.hellomoon.io/v0/nft/estimated-price";
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct NftEstimatedPriceResponse {
+    /// array of objects
+    pub data: Option<Vec<NftEstimatedPrice>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct NftEstimatedPrice {
+    /// Mint address of nft per the spl token program.
+    /// Each NFT has a unique mint address within the collection.
+    #[serde(rename = "nftMint")]
+    pub nft_mint: Option<String>,
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// The estimated price of the NFT, in lamports.
+    #[serde(rename = "estimatedPrice")]
+    pub estimated_price: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct NftEstimatedPriceRequest {
+    /// Mint address of nft per the spl token program.
+    /// Each NFT has a unique mint address within the collection.
+    #[serde(rename = "nftMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub nft_mint: String,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+pub async fn nft_estimated_price(
+    api_key: &str,
+    request: Option<NftEstimatedPriceRequest>,
+) -> anyhow::Result<NftEstimatedPriceResponse> {
+    core_call::<NftEstimatedPriceRequest, NftEstimatedPriceResponse>(
+        request,
+        NFT_ESTIMATED_PRICE_API_URL.to_string(),
+        api_key,
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_nft_estimated_price() {
+    use crate::dimension_facts::nft::collection_mint_mapping::{
+        collection_mint_mapping, CollectionMintMappingRequest,
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+    let mapping = CollectionMintMappingRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        limit: 1,
+        ..Default::default()
+    };
+    let nft_mint = collection_mint_mapping(&api_key, Some(mapping))
+        .await
+        .unwrap()
+        .data
+        .unwrap()
+        .remove(0)
+        .nft_mint
+        .unwrap();
+
+    let request = NftEstimatedPriceRequest {
+        nft_mint,
+        ..Default::default()
+    };
+    let left = nft_estimated_price(&api_key, Some(request)).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: NftEstimatedPriceResponse = serde_json::from_str(&r).unwrap();
+    assert_eq!(left, right);
+}
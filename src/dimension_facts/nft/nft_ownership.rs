@@ -0,0 +1,101 @@
+//! # NFT Ownership
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/current-owner`
+//!
+//! The NFT Ownership endpoint returns the current owner of an NFT mint.
+//!
+//! Query by a single nftMint, or by helloMoonCollectionId to receive the current owner of every mint in the collection.
+//! ownerAccount can also be used to check which mints of a collection are held by a wallet.
+use crate::{core_call, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+const NFT_OWNERSHIP_API_URL: &str = "https://rest-api.hellomoon.io/v0/nft/current-owner";
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct NftOwnershipResponse {
+    /// array of objects
+    pub data: Option<Vec<NftOwnership>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct NftOwnership {
+    /// Mint address of nft per the spl token program.
+    /// Each NFT has a unique mint address within the collection.
+    #[serde(rename = "nftMint")]
+    pub nft_mint: Option<String>,
+    /// The owner account of the NFT per the SPL token program
+    #[serde(rename = "ownerAccount")]
+    pub owner_account: Option<String>,
+    /// The token account of the NFT per the SPL token program
+    #[serde(rename = "tokenAccount")]
+    pub token_account: Option<String>,
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// Numeric identifier of the block in which the NFT was last transferred to the current owner
+    #[serde(rename = "blockId")]
+    pub block_id: Option<usize>,
+    /// Unix epoch time (in seconds) of the block in which the NFT was last transferred to the current owner
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct NftOwnershipRequest {
+    /// Mint address of nft per the spl token program.
+    /// Each NFT has a unique mint address within the collection.
+    #[serde(rename = "nftMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub nft_mint: String,
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The owner account of the NFT per the SPL token program
+    #[serde(rename = "ownerAccount")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub owner_account: String,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+pub async fn nft_ownership(
+    api_key: &str,
+    request: Option<NftOwnershipRequest>,
+) -> anyhow::Result<NftOwnershipResponse> {
+    core_call::<NftOwnershipRequest, NftOwnershipResponse>(
+        request,
+        NFT_OWNERSHIP_API_URL.to_string(),
+        api_key,
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_nft_ownership() {
+    let request = NftOwnershipRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+    let left = nft_ownership(&api_key, Some(request)).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: NftOwnershipResponse = serde_json::from_str(&r).unwrap();
+    assert_eq!(left, right);
+}
@@ -0,0 +1,177 @@
+//! # NFT Royalty
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/royalties`
+//!
+//! The NFT Royalty endpoint returns the royalty paid to the creators for every secondary sale of an NFT.
+//!
+//! Each row carries the sale price, the royalty that was actually paid and the sellerFeeBasisPoints of the NFT at the time of the sale,
+//! so royalty enforcement can be compared per marketplace.
+//!
+//! Data goes back 30 days from all supported markets.
+use crate::dimension_facts::nft::secondary_sales::{serialize_marketplace_filter, Marketplace};
+use crate::summary::common::BlockTimeRange;
+use crate::{core_call, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+const NFT_ROYALTY_API_URL: &str = "https://rest-api.hellomoon.io/v0/nft/royalties";
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct NftRoyaltyResponse {
+    /// array of objects
+    pub data: Option<Vec<NftRoyalty>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct NftRoyalty {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// The mint address of the NFT that was sold to the buyer by the seller.
+    #[serde(rename = "nftMint")]
+    pub nft_mint: Option<String>,
+    /// A list of marketplaces and their data supported by HelloMoon
+    ///
+    /// SMB ME_V1 ME_V2 YAWWW Elixir SolSea OpenSea Solanart Hadeswap CoralCube Coral Cube Exchange.Art
+    pub marketplace: Option<String>,
+    /// The price of the NFT that was sold to the buyer, in lamports.
+    pub price: Option<usize>,
+    /// The royalty paid to the creators of the NFT for this sale, in lamports.
+    #[serde(rename = "royaltyPaid")]
+    pub royalty_paid: Option<usize>,
+    /// The royalties shared by the creators in basis points — i.e. 550 means 5.5%.
+    #[serde(rename = "sellerFeeBasisPoints")]
+    pub seller_fee_basis_points: Option<usize>,
+    /// PublicKey of the seller of the NFT
+    pub seller: Option<String>,
+    /// PublicKey of the buyer of the NFT
+    pub buyer: Option<String>,
+    /// Unix epoch time (in seconds) of a block as calculated from validator votes.
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<usize>,
+    /// Numeric identifier of a block describing the slot that the block was produced in
+    #[serde(rename = "blockId")]
+    pub block_id: Option<usize>,
+    /// First signature in a transaction, which can be used to track and verify the transaction status across the complete ledger.
+    /// It is a base-58 encoded string that is uniquely generated for each transaction.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: Option<String>,
+}
+
+impl NftRoyalty {
+    /// The royalty the creators should have received for this sale, in lamports,
+    /// i.e. `price * sellerFeeBasisPoints / 10_000`. `None` when the product overflows.
+    pub fn expected_royalty(&self) -> Option<usize> {
+        Some(self.price?.checked_mul(self.seller_fee_basis_points?)? / 10_000)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct NftRoyaltyRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The mint address of the NFT that was sold to the buyer by the seller.
+    #[serde(rename = "nftMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub nft_mint: String,
+    /// A list of marketplaces and their data supported by HelloMoon
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_marketplace_filter")]
+    pub marketplace: Option<Marketplace>,
+    /// PublicKey of the seller of the NFT
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub seller: String,
+    /// PublicKey of the buyer of the NFT
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub buyer: String,
+    /// Only the sales with a block time matching the range, in epoch seconds
+    #[serde(rename = "blockTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_time: Option<BlockTimeRange>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+pub async fn nft_royalty(
+    api_key: &str,
+    request: Option<NftRoyaltyRequest>,
+) -> anyhow::Result<NftRoyaltyResponse> {
+    core_call::<NftRoyaltyRequest, NftRoyaltyResponse>(
+        request,
+        NFT_ROYALTY_API_URL.to_string(),
+        api_key,
+    )
+    .await
+}
+
+#[test]
+fn test_nft_royalty_expected_royalty() {
+    let royalty = NftRoyalty {
+        price: Some(2_000_000_000),
+        seller_fee_basis_points: Some(550),
+        ..Default::default()
+    };
+    assert_eq!(royalty.expected_royalty(), Some(110_000_000));
+
+    let royalty = NftRoyalty {
+        price: Some(2_000_000_000),
+        ..Default::default()
+    };
+    assert_eq!(royalty.expected_royalty(), None);
+
+    let royalty = NftRoyalty {
+        price: Some(usize::MAX / 2),
+        seller_fee_basis_points: Some(550),
+        ..Default::default()
+    };
+    assert_eq!(royalty.expected_royalty(), None);
+}
+
+#[test]
+fn test_nft_royalty_marketplace_filter() {
+    let request = NftRoyaltyRequest {
+        marketplace: Some(Marketplace::Hadeswap),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({ "marketplace": "HADESWAP" })
+    );
+
+    let request = NftRoyaltyRequest {
+        marketplace: Some(Marketplace::Other),
+        ..Default::default()
+    };
+    assert!(serde_json::to_string(&request).is_err());
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_nft_royalty() {
+    let request = NftRoyaltyRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        limit: 10,
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+    let left = nft_royalty(&api_key, Some(request)).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: NftRoyaltyResponse = serde_json::from_str(&r).unwrap();
+    assert_eq!(left, right);
+}
@@ -0,0 +1,298 @@
+//! # Off-chain Metadata
+//!
+//! The `uri` of the on-chain Metaplex Metadata points to an off-chain JSON file following the
+//! Metaplex Token Metadata standard, which holds the image, attributes and properties of the NFT.
+//!
+//! The JSON is loaded through a [`MetadataFetcher`], so it can be fetched over http with
+//! [`HttpFetcher`] or read from local files with [`FileFetcher`].
+use std::future::Future;
+use std::path::PathBuf;
+
+use crate::dimension_facts::nft::metaplex_metadata::{
+    metaplex_metadata, MetaplexMetadata, MetaplexMetadataRequest,
+};
+use serde::{Deserialize, Serialize};
+
+const IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+const ARWEAVE_GATEWAY: &str = "https://arweave.net/";
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
+pub struct OffChainMetadata {
+    /// Name of the asset
+    pub name: Option<String>,
+    /// Symbol of the asset
+    pub symbol: Option<String>,
+    /// Description of the asset
+    pub description: Option<String>,
+    /// The royalties shared by the creators in basis points, as declared off-chain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seller_fee_basis_points: Option<usize>,
+    /// URI pointing to the asset's logo
+    pub image: Option<String>,
+    /// URI pointing to the asset's animation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animation_url: Option<String>,
+    /// URI pointing to an external URL defining the asset — e.g. the game's main site
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_url: Option<String>,
+    /// Array of attributes defining the characteristics of the asset
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+    /// Additional properties that define the asset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Properties>,
+    /// Legacy collection name and family of the asset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection: Option<OffChainCollection>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
+pub struct Attribute {
+    /// The type of attribute
+    pub trait_type: Option<String>,
+    /// The value for that attribute, a string or a number
+    pub value: serde_json::Value,
+    /// How a numeric value should be displayed — e.g. `number`, `boost_percentage`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_type: Option<String>,
+}
+
+impl Attribute {
+    /// The value of the attribute as text, `None` when the value is null.
+    pub fn value_string(&self) -> Option<String> {
+        match &self.value {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(value) => Some(value.clone()),
+            value => Some(value.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
+pub struct Properties {
+    /// Additional files to include with the asset
+    #[serde(default)]
+    pub files: Vec<File>,
+    /// Type of asset: image, video, audio, vr or html
+    pub category: Option<String>,
+    /// Creators of the asset as declared off-chain
+    #[serde(default)]
+    pub creators: Vec<OffChainCreator>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
+pub struct File {
+    /// The file's URI
+    pub uri: Option<String>,
+    /// The file's MIME type
+    #[serde(rename = "type")]
+    pub file_type: Option<String>,
+    /// Whether the file is served from a CDN
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cdn: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
+pub struct OffChainCreator {
+    /// The publicKey of the creator
+    pub address: Option<String>,
+    /// The share of the royalties that the creator gets
+    pub share: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
+pub struct OffChainCollection {
+    /// Name of the collection
+    pub name: Option<String>,
+    /// Family of the collection
+    pub family: Option<String>,
+}
+
+/// Loads the off-chain JSON a metadata `uri` points to.
+pub trait MetadataFetcher {
+    fn fetch(&self, uri: &str) -> impl Future<Output = anyhow::Result<String>> + Send;
+}
+
+/// Fetch the JSON over http. `ipfs://` and `ar://` uris are rewritten to public gateways.
+#[derive(Debug, Default, Clone)]
+pub struct HttpFetcher {
+    client: reqwest::Client,
+}
+
+impl HttpFetcher {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl MetadataFetcher for HttpFetcher {
+    async fn fetch(&self, uri: &str) -> anyhow::Result<String> {
+        let url = if let Some(cid) = uri.strip_prefix("ipfs://") {
+            format!("{}{}", IPFS_GATEWAY, cid)
+        } else if let Some(id) = uri.strip_prefix("ar://") {
+            format!("{}{}", ARWEAVE_GATEWAY, id)
+        } else {
+            uri.to_string()
+        };
+        Ok(self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?)
+    }
+}
+
+/// Read the JSON from a local directory.
+///
+/// The last path segment of the uri is used as file name, `<root>/<segment>.json` is tried
+/// when `<root>/<segment>` does not exist. `https://arweave.net/abc` is read from `<root>/abc`.
+#[derive(Debug, Clone)]
+pub struct FileFetcher {
+    root: PathBuf,
+}
+
+impl FileFetcher {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl MetadataFetcher for FileFetcher {
+    async fn fetch(&self, uri: &str) -> anyhow::Result<String> {
+        let path = uri.split(['?', '#']).next().unwrap_or_default();
+        let Some(name) = path.rsplit('/').find(|segment| !segment.is_empty()) else {
+            anyhow::bail!("no file name in uri: {}", uri);
+        };
+        let path = self.root.join(name);
+        let path = if tokio::fs::try_exists(&path).await.unwrap_or_default() {
+            path
+        } else {
+            self.root.join(format!("{}.json", name))
+        };
+        Ok(tokio::fs::read_to_string(path).await?)
+    }
+}
+
+/// Metaplex Metadata together with its resolved off-chain JSON.
+#[derive(Debug, PartialEq)]
+pub struct ResolvedMetadata {
+    pub metadata: MetaplexMetadata,
+    /// `None` when the NFT has no uri or the JSON could not be loaded
+    pub off_chain: Option<OffChainMetadata>,
+}
+
+/// Load and parse the off-chain JSON of an NFT, `Ok(None)` when the NFT has no uri.
+pub async fn resolve_off_chain_metadata<F: MetadataFetcher>(
+    fetcher: &F,
+    metadata: &MetaplexMetadata,
+) -> anyhow::Result<Option<OffChainMetadata>> {
+    let Some(uri) = metadata.uri() else {
+        return Ok(None);
+    };
+    let json = fetcher.fetch(uri).await?;
+    Ok(Some(serde_json::from_str(&json)?))
+}
+
+/// Query the Metaplex Metadata endpoint and resolve the off-chain JSON of every returned NFT.
+///
+/// NFTs whose JSON can not be fetched or parsed are returned with `off_chain: None`.
+pub async fn metaplex_metadata_with_off_chain<F: MetadataFetcher>(
+    api_key: &str,
+    request: Option<MetaplexMetadataRequest>,
+    fetcher: &F,
+) -> anyhow::Result<Vec<ResolvedMetadata>> {
+    let response = metaplex_metadata(api_key, request).await?;
+
+    let mut resolved = vec![];
+    for metadata in response.data.unwrap_or_default() {
+        let off_chain = match resolve_off_chain_metadata(fetcher, &metadata).await {
+            Ok(off_chain) => off_chain,
+            Err(e) => {
+                log::warn!(
+                    "failed to resolve off-chain metadata of {:?}: {}",
+                    metadata.nft_mint,
+                    e
+                );
+                None
+            }
+        };
+        resolved.push(ResolvedMetadata {
+            metadata,
+            off_chain,
+        });
+    }
+    Ok(resolved)
+}
+
+#[tokio::test]
+async fn test_resolve_off_chain_metadata() {
+    use crate::dimension_facts::nft::metaplex_metadata::NftMetadataJson;
+
+    let root = std::env::temp_dir().join("hellomoon-rs-test-off-chain-metadata");
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(
+        root.join("abc.json"),
+        r#"{
+            "name": "Okay Bear #1",
+            "symbol": "okay_bears",
+            "image": "https://arweave.net/image.png",
+            "attributes": [
+                { "trait_type": "Fur", "value": "Brown" },
+                { "trait_type": "Level", "value": 3 }
+            ],
+            "properties": {
+                "files": [{ "uri": "https://arweave.net/image.png", "type": "image/png" }],
+                "category": "image",
+                "creators": [{ "address": "creator", "share": 100 }]
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let metadata = MetaplexMetadata {
+        nft_metadata_json: Some(NftMetadataJson {
+            uri: Some("https://arweave.net/abc".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let off_chain = resolve_off_chain_metadata(&FileFetcher::new(&root), &metadata)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(off_chain.name.as_deref(), Some("Okay Bear #1"));
+    assert_eq!(off_chain.attributes.len(), 2);
+    assert_eq!(off_chain.attributes[1].value_string().as_deref(), Some("3"));
+    let properties = off_chain.properties.unwrap();
+    assert_eq!(properties.files[0].file_type.as_deref(), Some("image/png"));
+    assert_eq!(properties.creators[0].share, Some(100));
+
+    let no_uri = MetaplexMetadata::default();
+    assert_eq!(
+        resolve_off_chain_metadata(&FileFetcher::new(&root), &no_uri)
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_metaplex_metadata_with_off_chain() {
+    let request = MetaplexMetadataRequest {
+        limit: 5,
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+    let resolved =
+        metaplex_metadata_with_off_chain(&api_key, Some(request), &HttpFetcher::default())
+            .await
+            .unwrap();
+
+    println!("{:#?}", resolved);
+}
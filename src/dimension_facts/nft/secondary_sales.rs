@@ -7,53 +7,54 @@
 //! For example, the Primary Sales endpoint visualizes who minted the NFT and the program that was used. While the Secondary Sales endpoint visualizes who the minter sold the NFT to and the price it was transacted at.
 //!
 //! Data goes back 30 days for supported launchpads and mint programs.
+use crate::summary::common::BlockTimeRange;
 use crate::{core_call, limit_is_zero, page_is_zero};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 const SECONDARY_SALES_API_URL: &str = "https://rest-api.hellomoon.io/v0/nft/sales/secondary";
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
 pub struct SecondarySalesResponse {
     /// array of objects
-    data: Option<Vec<SecondarySales>>,
+    pub data: Option<Vec<SecondarySales>>,
     /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
 pub struct SecondarySales {
     /// Numeric identifier of a block describing the slot that the block was produced in
     #[serde(rename = "blockId")]
-    block_id: Option<String>,
+    pub block_id: Option<String>,
     /// A list of marketplaces and their data supported by HelloMoon
     ///
     /// SMB ME_V1 ME_V2 YAWWW Elixir SolSea OpenSea Solanart Hadeswap CoralCube Coral Cube Exchange.Art
-    marketplace: Option<String>,
+    pub marketplace: Option<String>,
     ///Unix epoch time (in seconds) of a block as calculated from validator votes.
     /// If you want to look at historical data, let's say 7 days in the past.
     /// 1. Change the operator to <
     /// 2. Get the current epochtime i.e, 1673831466 -> Jan 15, 2023
     /// 3. Subtract the current epochtime from ( 86400 * 7 ). Place the result of 1673831466 - ( 86400 * 7 ) = 1673226666 in the value input - this returns the epochtime time from 7 days ago
     #[serde(rename = "blockTime")]
-    block_time: Option<String>,
+    pub block_time: Option<String>,
     /// The mint address of the NFT that was sold to the buyer by the seller.
     #[serde(rename = "nftMint")]
-    nft_mint: Option<String>,
+    pub nft_mint: Option<String>,
     /// PublicKey of the seller of the NFT
-    seller: Option<String>,
+    pub seller: Option<String>,
     /// PublicKey of the buyer of the NFT
-    buyer: Option<String>,
+    pub buyer: Option<String>,
     /// The price of the NFT that was sold to the buyer.
     /// price is denoted in Solana
-    price: Option<String>,
+    pub price: Option<String>,
     /// First signature in a transaction, which can be used to track and verify the transaction status across the complete ledger.
     /// It is a base-58 encoded string that is uniquely generated for each transaction.
     #[serde(rename = "transactionId")]
-    transaction_id: Option<String>,
+    pub transaction_id: Option<String>,
     /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
     #[serde(rename = "helloMoonCollectionId")]
-    hello_moon_collection_id: Option<String>,
+    pub hello_moon_collection_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
@@ -63,49 +64,52 @@ pub struct SecondarySalesRequest {
     /// This list is continuously updated.
     #[serde(rename = "helloMoonCollectionId")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    hello_moon_collection_id: String,
+    pub hello_moon_collection_id: String,
     /// The mint address of the NFT that was sold to the buyer by the seller.
-    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(rename = "nftMint")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    nft_mint: String,
+    pub nft_mint: String,
     /// PublicKey of the buyer of the NFT
     #[serde(skip_serializing_if = "String::is_empty")]
-    buyer: String,
+    pub buyer: String,
     /// PublicKey of the seller of the NFT
     #[serde(skip_serializing_if = "String::is_empty")]
-    seller: String,
+    pub seller: String,
     /// A list of marketplaces and their data supported by HelloMoon
     #[serde(skip_serializing_if = "marketplace_is_empty")]
-    marketplace: Option<Marketplace>,
+    #[serde(serialize_with = "serialize_marketplace_filter")]
+    pub marketplace: Option<Marketplace>,
     /// The price of the NFT that was sold to the buyer.
     /// price is denoted in Solana
     #[serde(skip_serializing_if = "String::is_empty")]
-    price: String,
+    pub price: String,
     /// Unix epoch time (in seconds) of a block as calculated from validator votes.
     /// If you want to look at historical data, let's say 7 days in the past.
     /// 1. Change the operator to <
     /// 2. Get the current epochtime i.e, 1673831466 -> Jan 15, 2023
     /// 3. Subtract the current epochtime from ( 86400 * 7 ). Place the result of 1673831466 - ( 86400 * 7 ) = 1673226666 in the value input - this returns the epochtime time from 7 days ago
     #[serde(rename = "blockTime")]
-    #[serde(skip_serializing_if = "String::is_empty")]
-    block_time: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_time: Option<BlockTimeRange>,
     /// Numeric identifier of a block describing the slot that the block was produced in
     #[serde(rename = "blockId")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    block_id: String,
+    pub block_id: String,
     /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
     /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
     /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(
+    Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default,
+)]
 pub enum Marketplace {
     #[serde(rename = "SMB")]
     Smb,
@@ -115,6 +119,7 @@ pub enum Marketplace {
     Mev2,
     #[serde(rename = "YAWWW")]
     Yawww,
+    #[default]
     #[serde(rename = "ELIXIR")]
     Elixir,
     #[serde(rename = "SOLSEA")]
@@ -131,12 +136,24 @@ pub enum Marketplace {
     CoralCube,
     #[serde(rename = "Exchange.art")]
     ExchangeArt,
+    /// A marketplace not listed above, only returned by the api.
+    /// It can not be used as a filter, requests with it fail to serialize.
+    #[serde(other)]
+    Other,
 }
 
-impl Default for Marketplace {
-    fn default() -> Self {
-        Self::Elixir
+/// Serialize the marketplace filter of a request, failing on `Marketplace::Other` which the api
+/// does not know.
+pub(crate) fn serialize_marketplace_filter<S: Serializer>(
+    value: &Option<Marketplace>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if *value == Some(Marketplace::Other) {
+        return Err(serde::ser::Error::custom(
+            "Marketplace::Other can not be used as a filter",
+        ));
     }
+    value.serialize(serializer)
 }
 
 fn marketplace_is_empty(value: &Option<Marketplace>) -> bool {
@@ -162,6 +179,28 @@ pub async fn scondary_sales(
     .await
 }
 
+#[test]
+fn test_marketplace_filter() {
+    let request = SecondarySalesRequest {
+        marketplace: Some(Marketplace::Mev2),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({ "marketplace": "ME_V2" })
+    );
+
+    let request = SecondarySalesRequest {
+        marketplace: Some(Marketplace::Other),
+        ..Default::default()
+    };
+    assert!(serde_json::to_string(&request).is_err());
+    assert_eq!(
+        serde_json::to_string(&Marketplace::Other).unwrap(),
+        r#""Other""#
+    );
+}
+
 #[tokio::test]
 #[ignore = "reason"]
 async fn test_scondary_sales() {
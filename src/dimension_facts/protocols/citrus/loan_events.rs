@@ -0,0 +1,192 @@
+//! # Citrus Loan Events
+//!
+//! POST `https://rest-api.hellomoon.io/v0/citrus/loan-events`
+//!
+//! Every instruction of the Citrus NFT lending program: lenders post offers on a collection,
+//! borrowers take them by locking an NFT as collateral, and repay the loan or lose the NFT to
+//! the lender when the duration is over. Amounts are in lamports.
+use crate::dimension_facts::protocols::{serialize_action_filter, ActionType};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CitrusLoanEventsRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The address of the loan account
+    #[serde(rename = "loanAccount")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub loan_account: String,
+    /// PublicKey of the lender
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub lender: String,
+    /// PublicKey of the borrower
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub borrower: String,
+    /// The mint address of the NFT locked as collateral
+    #[serde(rename = "collateralMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub collateral_mint: String,
+    #[serde(rename = "actionType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_action_filter")]
+    pub action_type: Option<CitrusLoanAction>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+/// The step of the loan an event is about.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum CitrusLoanAction {
+    /// A lender posted an offer
+    #[serde(rename = "offer")]
+    Offer,
+    /// A lender cancelled an offer that was not taken
+    #[serde(rename = "cancelOffer")]
+    CancelOffer,
+    /// A borrower took an offer and locked the collateral
+    #[serde(rename = "borrow")]
+    Borrow,
+    /// The borrower repaid the loan and got the collateral back
+    #[serde(rename = "repay")]
+    Repay,
+    /// The lender claimed the collateral of an overdue loan
+    #[serde(rename = "claim")]
+    Claim,
+    /// An instruction not listed above
+    #[serde(other)]
+    Other,
+}
+
+impl ActionType for CitrusLoanAction {
+    const OTHER: Self = CitrusLoanAction::Other;
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CitrusLoanEventsResponse {
+    /// array of objects
+    pub data: Option<Vec<CitrusLoanEvent>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CitrusLoanEvent {
+    /// Numeric identifier of a block describing the slot that the block was produced in
+    #[serde(rename = "blockId")]
+    pub block_id: Option<usize>,
+    /// Unix epoch time (in seconds) of a block as calculated from validator votes.
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<usize>,
+    /// First signature in a transaction, which can be used to track and verify the transaction status across the complete ledger.
+    /// It is a base-58 encoded string that is uniquely generated for each transaction.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: Option<String>,
+    #[serde(rename = "actionType")]
+    pub action_type: Option<CitrusLoanAction>,
+    /// The address of the loan account
+    #[serde(rename = "loanAccount")]
+    pub loan_account: Option<String>,
+    /// PublicKey of the lender
+    pub lender: Option<String>,
+    /// PublicKey of the borrower, empty while the offer is not taken
+    pub borrower: Option<String>,
+    /// The mint address of the NFT locked as collateral
+    #[serde(rename = "collateralMint")]
+    pub collateral_mint: Option<String>,
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// Amount lent, in lamports
+    #[serde(rename = "loanAmount")]
+    pub loan_amount: Option<u64>,
+    /// Annual percentage rate of the loan, in basis points
+    #[serde(rename = "aprBps")]
+    pub apr_bps: Option<u64>,
+    /// Duration of the loan, in seconds
+    pub duration: Option<u64>,
+    /// Amount paid back to the lender on repayment, in lamports
+    #[serde(rename = "repaidAmount")]
+    pub repaid_amount: Option<u64>,
+}
+
+impl CitrusLoanEvent {
+    pub fn loan_amount_sol(&self) -> Option<f64> {
+        self.loan_amount.map(lamports_to_sol)
+    }
+
+    /// Annual percentage rate, in percent.
+    pub fn apr(&self) -> Option<f64> {
+        self.apr_bps.map(|apr_bps| apr_bps as f64 / 100.0)
+    }
+}
+
+pub async fn citrus_loan_events(
+    request: Option<CitrusLoanEventsRequest>,
+    api_key: &str,
+) -> anyhow::Result<CitrusLoanEventsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/citrus/loan-events");
+    core_call::<CitrusLoanEventsRequest, CitrusLoanEventsResponse>(request, api_url, api_key).await
+}
+
+#[test]
+fn test_citrus_loan_event_serde() {
+    let event: CitrusLoanEvent = serde_json::from_str(
+        r#"{"actionType": "borrow", "loanAmount": 2500000000, "aprBps": 12000, "duration": 604800}"#,
+    )
+    .unwrap();
+    assert_eq!(event.action_type, Some(CitrusLoanAction::Borrow));
+    assert_eq!(event.loan_amount_sol(), Some(2.5));
+    assert_eq!(event.apr(), Some(120.0));
+
+    let event: CitrusLoanEvent = serde_json::from_str(r#"{"actionType": "updateOffer"}"#).unwrap();
+    assert_eq!(event.action_type, Some(CitrusLoanAction::Other));
+
+    let request = CitrusLoanEventsRequest {
+        action_type: Some(CitrusLoanAction::Claim),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({ "actionType": "claim" })
+    );
+
+    let request = CitrusLoanEventsRequest {
+        action_type: Some(CitrusLoanAction::Other),
+        ..Default::default()
+    };
+    assert!(serde_json::to_string(&request).is_err());
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_citrus_loan_events() {
+    let request = CitrusLoanEventsRequest {
+        action_type: Some(CitrusLoanAction::Borrow),
+        limit: 10,
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = citrus_loan_events(Some(request), &api_key).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: CitrusLoanEventsResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
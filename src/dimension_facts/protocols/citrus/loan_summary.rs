@@ -0,0 +1,157 @@
+//! # Citrus Loan Summary
+//!
+//! POST `https://rest-api.hellomoon.io/v0/citrus/loan-summary`
+//!
+//! Citrus loans of a collection per day, week or month: offers, loans taken, repaid and claimed
+//! by the lender, and the amount lent. Amounts are in lamports.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CitrusLoanSummaryRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the loans are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CitrusLoanSummaryResponse {
+    /// array of objects
+    pub data: Option<Vec<CitrusLoanSummary>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CitrusLoanSummary {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of offers posted
+    #[serde(rename = "offersCount")]
+    pub offers_count: Option<u64>,
+    /// Number of loans taken
+    #[serde(rename = "loansCount")]
+    pub loans_count: Option<u64>,
+    /// Number of loans repaid
+    #[serde(rename = "loansRepaid")]
+    pub loans_repaid: Option<u64>,
+    /// Number of overdue loans whose collateral was claimed by the lender
+    #[serde(rename = "loansClaimed")]
+    pub loans_claimed: Option<u64>,
+    /// Amount lent, in lamports
+    pub volume: Option<u64>,
+    /// Average annual percentage rate of the loans taken, in basis points
+    #[serde(rename = "averageAprBps")]
+    pub average_apr_bps: Option<f64>,
+    /// Average duration of the loans taken, in seconds
+    #[serde(rename = "averageDuration")]
+    pub average_duration: Option<f64>,
+}
+
+impl CitrusLoanSummary {
+    pub fn volume_sol(&self) -> Option<f64> {
+        self.volume.map(lamports_to_sol)
+    }
+
+    /// Share of the loans closed in the period that were claimed by the lender, between 0 and 1.
+    pub fn default_rate(&self) -> Option<f64> {
+        let claimed = self.loans_claimed?;
+        let closed = claimed + self.loans_repaid?;
+        (closed > 0).then(|| claimed as f64 / closed as f64)
+    }
+}
+
+pub async fn citrus_loan_summary(
+    request: Option<CitrusLoanSummaryRequest>,
+    api_key: &str,
+) -> anyhow::Result<CitrusLoanSummaryResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/citrus/loan-summary");
+    core_call::<CitrusLoanSummaryRequest, CitrusLoanSummaryResponse>(request, api_url, api_key)
+        .await
+}
+
+/// Citrus loans of a collection per period matching `day`.
+pub async fn citrus_loan_summary_time_series(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<CitrusLoanSummary>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = CitrusLoanSummaryRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = citrus_loan_summary(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(TimeSeries::from_rows(granularity, rows, |row| {
+        Some((row.day.clone()?, row))
+    }))
+}
+
+#[test]
+fn test_citrus_default_rate() {
+    let summary = CitrusLoanSummary {
+        loans_repaid: Some(9),
+        loans_claimed: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(summary.default_rate(), Some(0.1));
+    let summary = CitrusLoanSummary {
+        loans_repaid: Some(0),
+        loans_claimed: Some(0),
+        ..Default::default()
+    };
+    assert_eq!(summary.default_rate(), None);
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_citrus_loan_summary() {
+    let request = CitrusLoanSummaryRequest {
+        day: Some(DateRange::since("2023-03-01")),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = citrus_loan_summary(Some(request), &api_key).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: CitrusLoanSummaryResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
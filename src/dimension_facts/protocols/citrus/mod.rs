@@ -0,0 +1,2 @@
+pub mod loan_events;
+pub mod loan_summary;
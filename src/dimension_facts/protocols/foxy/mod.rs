@@ -0,0 +1 @@
+pub mod raffle_events;
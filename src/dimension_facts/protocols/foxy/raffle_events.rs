@@ -0,0 +1,201 @@
+//! # Foxy Raffle Events
+//!
+//! POST `https://rest-api.hellomoon.io/v0/foxy/raffle-events`
+//!
+//! Every instruction of the Foxy raffle program: a creator puts an NFT up as the prize of a
+//! raffle, participants buy tickets, and the winner is drawn when the raffle ends.
+//! Ticket prices are in the smallest unit of the ticket token, lamports for SOL.
+use crate::dimension_facts::protocols::{serialize_action_filter, ActionType};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FoxyRaffleEventsRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The address of the raffle account
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub raffle: String,
+    /// PublicKey of the wallet that signed the instruction: the creator, a ticket buyer or the winner
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub wallet: String,
+    /// The mint address of the NFT raffled
+    #[serde(rename = "prizeMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub prize_mint: String,
+    #[serde(rename = "actionType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_action_filter")]
+    pub action_type: Option<FoxyRaffleAction>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+/// The step of the raffle an event is about.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum FoxyRaffleAction {
+    /// The creator opened the raffle and escrowed the prize
+    #[serde(rename = "createRaffle")]
+    CreateRaffle,
+    /// A participant bought tickets
+    #[serde(rename = "buyTickets")]
+    BuyTickets,
+    /// The winning ticket was drawn
+    #[serde(rename = "drawWinner")]
+    DrawWinner,
+    /// The winner claimed the prize
+    #[serde(rename = "claimPrize")]
+    ClaimPrize,
+    /// The creator closed the raffle and got the prize back, no ticket was sold
+    #[serde(rename = "cancelRaffle")]
+    CancelRaffle,
+    /// An instruction not listed above
+    #[serde(other)]
+    Other,
+}
+
+impl ActionType for FoxyRaffleAction {
+    const OTHER: Self = FoxyRaffleAction::Other;
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FoxyRaffleEventsResponse {
+    /// array of objects
+    pub data: Option<Vec<FoxyRaffleEvent>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct FoxyRaffleEvent {
+    /// Numeric identifier of a block describing the slot that the block was produced in
+    #[serde(rename = "blockId")]
+    pub block_id: Option<usize>,
+    /// Unix epoch time (in seconds) of a block as calculated from validator votes.
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<usize>,
+    /// First signature in a transaction, which can be used to track and verify the transaction status across the complete ledger.
+    /// It is a base-58 encoded string that is uniquely generated for each transaction.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: Option<String>,
+    #[serde(rename = "actionType")]
+    pub action_type: Option<FoxyRaffleAction>,
+    /// The address of the raffle account
+    pub raffle: Option<String>,
+    /// PublicKey of the wallet that signed the instruction
+    pub wallet: Option<String>,
+    /// PublicKey of the creator of the raffle
+    pub creator: Option<String>,
+    /// The mint address of the NFT raffled
+    #[serde(rename = "prizeMint")]
+    pub prize_mint: Option<String>,
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// The mint of the token tickets are paid with
+    #[serde(rename = "ticketMint")]
+    pub ticket_mint: Option<String>,
+    /// Price of a ticket, in the smallest unit of the ticket token
+    #[serde(rename = "ticketPrice")]
+    pub ticket_price: Option<u64>,
+    /// Number of tickets bought in the instruction
+    #[serde(rename = "ticketCount")]
+    pub ticket_count: Option<u64>,
+    /// Maximum number of tickets of the raffle
+    #[serde(rename = "maxTickets")]
+    pub max_tickets: Option<u64>,
+    /// Unix epoch time (in seconds) the raffle ends at
+    #[serde(rename = "endTime")]
+    pub end_time: Option<usize>,
+    /// PublicKey of the winner, set by the draw
+    pub winner: Option<String>,
+}
+
+impl FoxyRaffleEvent {
+    /// Amount paid for the tickets of the instruction, in the smallest unit of the ticket token.
+    ///
+    /// `None` when it does not fit in a `u64`.
+    pub fn amount_paid(&self) -> Option<u64> {
+        self.ticket_price?.checked_mul(self.ticket_count?)
+    }
+
+    /// `amount_paid` in SOL, for raffles with tickets paid in SOL.
+    pub fn amount_paid_sol(&self) -> Option<f64> {
+        self.amount_paid().map(lamports_to_sol)
+    }
+}
+
+pub async fn foxy_raffle_events(
+    request: Option<FoxyRaffleEventsRequest>,
+    api_key: &str,
+) -> anyhow::Result<FoxyRaffleEventsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/foxy/raffle-events");
+    core_call::<FoxyRaffleEventsRequest, FoxyRaffleEventsResponse>(request, api_url, api_key).await
+}
+
+#[test]
+fn test_foxy_raffle_event_serde() {
+    let event: FoxyRaffleEvent = serde_json::from_str(
+        r#"{"actionType": "buyTickets", "ticketPrice": 50000000, "ticketCount": 4}"#,
+    )
+    .unwrap();
+    assert_eq!(event.action_type, Some(FoxyRaffleAction::BuyTickets));
+    assert_eq!(event.amount_paid(), Some(200_000_000));
+    assert_eq!(event.amount_paid_sol(), Some(0.2));
+
+    let event = FoxyRaffleEvent {
+        ticket_price: Some(u64::MAX),
+        ticket_count: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(event.amount_paid(), None);
+
+    let request = FoxyRaffleEventsRequest {
+        wallet: "wallet".to_string(),
+        action_type: Some(FoxyRaffleAction::DrawWinner),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({ "wallet": "wallet", "actionType": "drawWinner" })
+    );
+
+    let request = FoxyRaffleEventsRequest {
+        action_type: Some(FoxyRaffleAction::Other),
+        ..Default::default()
+    };
+    assert!(serde_json::to_string(&request).is_err());
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_foxy_raffle_events() {
+    let request = FoxyRaffleEventsRequest {
+        action_type: Some(FoxyRaffleAction::CreateRaffle),
+        limit: 10,
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = foxy_raffle_events(Some(request), &api_key).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: FoxyRaffleEventsResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
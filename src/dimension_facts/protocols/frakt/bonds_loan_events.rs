@@ -0,0 +1,196 @@
+//! # Frakt Bonds Loan Events
+//!
+//! POST `https://rest-api.hellomoon.io/v0/frakt/bonds/loan-events`
+//!
+//! Every instruction of the Frakt bonds program: a borrower sells a bond backed by an NFT to a
+//! lender's offer, then repays it before it expires or the collateral is liquidated, and the
+//! lender redeems the bond tokens. Amounts are in lamports.
+use crate::dimension_facts::protocols::{serialize_action_filter, ActionType};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FraktBondsLoanEventsRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The address of the bond account
+    #[serde(rename = "fraktBond")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub frakt_bond: String,
+    /// PublicKey of the borrower
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub borrower: String,
+    /// PublicKey of the lender
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub lender: String,
+    /// The mint address of the NFT locked as collateral
+    #[serde(rename = "collateralMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub collateral_mint: String,
+    #[serde(rename = "actionType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_action_filter")]
+    pub action_type: Option<FraktBondAction>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+/// The step of the bond an event is about.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum FraktBondAction {
+    /// A borrower sold a bond to an offer and locked the collateral
+    #[serde(rename = "borrow")]
+    Borrow,
+    /// The borrower repaid the bond and got the collateral back
+    #[serde(rename = "repay")]
+    Repay,
+    /// The bond expired and the collateral was seized
+    #[serde(rename = "liquidate")]
+    Liquidate,
+    /// The lender redeemed the bond tokens
+    #[serde(rename = "redeem")]
+    Redeem,
+    /// An instruction not listed above
+    #[serde(other)]
+    Other,
+}
+
+impl ActionType for FraktBondAction {
+    const OTHER: Self = FraktBondAction::Other;
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FraktBondsLoanEventsResponse {
+    /// array of objects
+    pub data: Option<Vec<FraktBondsLoanEvent>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct FraktBondsLoanEvent {
+    /// Numeric identifier of a block describing the slot that the block was produced in
+    #[serde(rename = "blockId")]
+    pub block_id: Option<usize>,
+    /// Unix epoch time (in seconds) of a block as calculated from validator votes.
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<usize>,
+    /// First signature in a transaction, which can be used to track and verify the transaction status across the complete ledger.
+    /// It is a base-58 encoded string that is uniquely generated for each transaction.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: Option<String>,
+    #[serde(rename = "actionType")]
+    pub action_type: Option<FraktBondAction>,
+    /// The address of the bond account
+    #[serde(rename = "fraktBond")]
+    pub frakt_bond: Option<String>,
+    /// The address of the offer the bond was sold to
+    #[serde(rename = "bondOffer")]
+    pub bond_offer: Option<String>,
+    /// PublicKey of the borrower
+    pub borrower: Option<String>,
+    /// PublicKey of the lender
+    pub lender: Option<String>,
+    /// The mint address of the NFT locked as collateral
+    #[serde(rename = "collateralMint")]
+    pub collateral_mint: Option<String>,
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// Amount borrowed, in lamports
+    #[serde(rename = "borrowedAmount")]
+    pub borrowed_amount: Option<u64>,
+    /// Amount to repay at expiration, in lamports
+    #[serde(rename = "repayAmount")]
+    pub repay_amount: Option<u64>,
+    /// Number of bond tokens sold
+    #[serde(rename = "bondTokenAmount")]
+    pub bond_token_amount: Option<u64>,
+    /// Unix epoch time (in seconds) the bond expires at
+    #[serde(rename = "expiredAt")]
+    pub expired_at: Option<usize>,
+}
+
+impl FraktBondsLoanEvent {
+    pub fn borrowed_amount_sol(&self) -> Option<f64> {
+        self.borrowed_amount.map(lamports_to_sol)
+    }
+
+    /// Seconds from the event to the expiration of the bond.
+    pub fn duration(&self) -> Option<usize> {
+        self.expired_at?.checked_sub(self.block_time?)
+    }
+}
+
+pub async fn frakt_bonds_loan_events(
+    request: Option<FraktBondsLoanEventsRequest>,
+    api_key: &str,
+) -> anyhow::Result<FraktBondsLoanEventsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/frakt/bonds/loan-events");
+    core_call::<FraktBondsLoanEventsRequest, FraktBondsLoanEventsResponse>(
+        request, api_url, api_key,
+    )
+    .await
+}
+
+#[test]
+fn test_frakt_bonds_loan_event_duration() {
+    let event = FraktBondsLoanEvent {
+        action_type: Some(FraktBondAction::Borrow),
+        block_time: Some(1_680_000_000),
+        expired_at: Some(1_680_000_000 + 86400 * 7),
+        ..Default::default()
+    };
+    assert_eq!(event.duration(), Some(86400 * 7));
+    assert_eq!(FraktBondsLoanEvent::default().duration(), None);
+
+    let request = FraktBondsLoanEventsRequest {
+        action_type: Some(FraktBondAction::Redeem),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({ "actionType": "redeem" })
+    );
+
+    let request = FraktBondsLoanEventsRequest {
+        action_type: Some(FraktBondAction::Other),
+        ..Default::default()
+    };
+    assert!(serde_json::to_string(&request).is_err());
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_frakt_bonds_loan_events() {
+    let request = FraktBondsLoanEventsRequest {
+        action_type: Some(FraktBondAction::Borrow),
+        limit: 10,
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = frakt_bonds_loan_events(Some(request), &api_key)
+        .await
+        .unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: FraktBondsLoanEventsResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
@@ -0,0 +1,203 @@
+//! # Frakt Loan Events
+//!
+//! POST `https://rest-api.hellomoon.io/v0/frakt/loan-events`
+//!
+//! Every instruction of the Frakt pool lending program: borrowers propose a loan against an NFT,
+//! the liquidity pool approves it, and the loan is paid back or liquidated. Time based loans are
+//! liquidated after their return period, price based loans when the collateral floor drops.
+//! Amounts are in lamports.
+use crate::dimension_facts::protocols::{serialize_action_filter, ActionType};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FraktLoanEventsRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The address of the loan account
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub loan: String,
+    /// PublicKey of the borrower
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub user: String,
+    /// The address of the liquidity pool lending
+    #[serde(rename = "liquidityPool")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub liquidity_pool: String,
+    /// The mint address of the NFT locked as collateral
+    #[serde(rename = "nftMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub nft_mint: String,
+    #[serde(rename = "actionType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_action_filter")]
+    pub action_type: Option<FraktLoanAction>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+/// The step of the loan an event is about.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum FraktLoanAction {
+    /// A borrower asked for a loan and locked the collateral
+    #[serde(rename = "proposeLoan")]
+    ProposeLoan,
+    /// The pool approved the loan and sent the funds
+    #[serde(rename = "approveLoan")]
+    ApproveLoan,
+    /// The pool rejected the loan and the collateral was returned
+    #[serde(rename = "rejectLoan")]
+    RejectLoan,
+    /// The borrower paid the loan back and got the collateral back
+    #[serde(rename = "paybackLoan")]
+    PaybackLoan,
+    /// The collateral was seized
+    #[serde(rename = "liquidateLoan")]
+    LiquidateLoan,
+    /// An instruction not listed above
+    #[serde(other)]
+    Other,
+}
+
+impl ActionType for FraktLoanAction {
+    const OTHER: Self = FraktLoanAction::Other;
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum FraktLoanType {
+    #[serde(rename = "timeBased")]
+    TimeBased,
+    #[serde(rename = "priceBased")]
+    PriceBased,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FraktLoanEventsResponse {
+    /// array of objects
+    pub data: Option<Vec<FraktLoanEvent>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct FraktLoanEvent {
+    /// Numeric identifier of a block describing the slot that the block was produced in
+    #[serde(rename = "blockId")]
+    pub block_id: Option<usize>,
+    /// Unix epoch time (in seconds) of a block as calculated from validator votes.
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<usize>,
+    /// First signature in a transaction, which can be used to track and verify the transaction status across the complete ledger.
+    /// It is a base-58 encoded string that is uniquely generated for each transaction.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: Option<String>,
+    #[serde(rename = "actionType")]
+    pub action_type: Option<FraktLoanAction>,
+    #[serde(rename = "loanType")]
+    pub loan_type: Option<FraktLoanType>,
+    /// The address of the loan account
+    pub loan: Option<String>,
+    /// PublicKey of the borrower
+    pub user: Option<String>,
+    /// The address of the liquidity pool lending
+    #[serde(rename = "liquidityPool")]
+    pub liquidity_pool: Option<String>,
+    /// The mint address of the NFT locked as collateral
+    #[serde(rename = "nftMint")]
+    pub nft_mint: Option<String>,
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// Value of the collateral when the loan was proposed, in lamports
+    #[serde(rename = "originalPrice")]
+    pub original_price: Option<u64>,
+    /// Amount lent, in lamports
+    #[serde(rename = "loanValue")]
+    pub loan_value: Option<u64>,
+    /// Amount to pay back, fees included, in lamports
+    #[serde(rename = "repayValue")]
+    pub repay_value: Option<u64>,
+    /// Days to pay a time based loan back
+    #[serde(rename = "returnPeriodDays")]
+    pub return_period_days: Option<u64>,
+}
+
+impl FraktLoanEvent {
+    pub fn loan_value_sol(&self) -> Option<f64> {
+        self.loan_value.map(lamports_to_sol)
+    }
+
+    /// Loan to value of the collateral when the loan was proposed, between 0 and 1.
+    pub fn ltv(&self) -> Option<f64> {
+        let original_price = self.original_price.filter(|price| *price > 0)?;
+        Some(self.loan_value? as f64 / original_price as f64)
+    }
+}
+
+pub async fn frakt_loan_events(
+    request: Option<FraktLoanEventsRequest>,
+    api_key: &str,
+) -> anyhow::Result<FraktLoanEventsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/frakt/loan-events");
+    core_call::<FraktLoanEventsRequest, FraktLoanEventsResponse>(request, api_url, api_key).await
+}
+
+#[test]
+fn test_frakt_loan_event_serde() {
+    let event: FraktLoanEvent = serde_json::from_str(
+        r#"{"actionType": "approveLoan", "loanType": "timeBased", "originalPrice": 4000000000, "loanValue": 1600000000, "returnPeriodDays": 14}"#,
+    )
+    .unwrap();
+    assert_eq!(event.action_type, Some(FraktLoanAction::ApproveLoan));
+    assert_eq!(event.loan_type, Some(FraktLoanType::TimeBased));
+    assert_eq!(event.loan_value_sol(), Some(1.6));
+    assert_eq!(event.ltv(), Some(0.4));
+
+    let request = FraktLoanEventsRequest {
+        action_type: Some(FraktLoanAction::LiquidateLoan),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({ "actionType": "liquidateLoan" })
+    );
+
+    let request = FraktLoanEventsRequest {
+        action_type: Some(FraktLoanAction::Other),
+        ..Default::default()
+    };
+    assert!(serde_json::to_string(&request).is_err());
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_frakt_loan_events() {
+    let request = FraktLoanEventsRequest {
+        action_type: Some(FraktLoanAction::LiquidateLoan),
+        limit: 10,
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = frakt_loan_events(Some(request), &api_key).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: FraktLoanEventsResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
@@ -0,0 +1,141 @@
+//! # Frakt Loan Summary
+//!
+//! POST `https://rest-api.hellomoon.io/v0/frakt/loan-summary`
+//!
+//! Frakt pool loans of a collection per day, week or month: loans issued, paid back and
+//! liquidated, split by loan type, and the amount lent. Amounts are in lamports.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FraktLoanSummaryRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the loans are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FraktLoanSummaryResponse {
+    /// array of objects
+    pub data: Option<Vec<FraktLoanSummary>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct FraktLoanSummary {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of loans approved
+    #[serde(rename = "loansIssued")]
+    pub loans_issued: Option<u64>,
+    /// Number of time based loans approved
+    #[serde(rename = "timeBasedLoans")]
+    pub time_based_loans: Option<u64>,
+    /// Number of price based loans approved
+    #[serde(rename = "priceBasedLoans")]
+    pub price_based_loans: Option<u64>,
+    /// Number of loans paid back
+    #[serde(rename = "loansPaidBack")]
+    pub loans_paid_back: Option<u64>,
+    /// Number of loans liquidated
+    #[serde(rename = "loansLiquidated")]
+    pub loans_liquidated: Option<u64>,
+    /// Amount lent, in lamports
+    pub volume: Option<u64>,
+    /// Average loan to value of the loans issued, in percent
+    #[serde(rename = "averageLtv")]
+    pub average_ltv: Option<f64>,
+}
+
+impl FraktLoanSummary {
+    pub fn volume_sol(&self) -> Option<f64> {
+        self.volume.map(lamports_to_sol)
+    }
+
+    /// Share of the loans closed in the period that were liquidated, between 0 and 1.
+    pub fn liquidation_rate(&self) -> Option<f64> {
+        let liquidated = self.loans_liquidated?;
+        let closed = liquidated + self.loans_paid_back?;
+        (closed > 0).then(|| liquidated as f64 / closed as f64)
+    }
+}
+
+pub async fn frakt_loan_summary(
+    request: Option<FraktLoanSummaryRequest>,
+    api_key: &str,
+) -> anyhow::Result<FraktLoanSummaryResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/frakt/loan-summary");
+    core_call::<FraktLoanSummaryRequest, FraktLoanSummaryResponse>(request, api_url, api_key).await
+}
+
+/// Frakt pool loans of a collection per period matching `day`.
+pub async fn frakt_loan_summary_time_series(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<FraktLoanSummary>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = FraktLoanSummaryRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = frakt_loan_summary(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(TimeSeries::from_rows(granularity, rows, |row| {
+        Some((row.day.clone()?, row))
+    }))
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_frakt_loan_summary() {
+    let request = FraktLoanSummaryRequest {
+        day: Some(DateRange::since("2023-01-01")),
+        granularity: Some(Granularity::OneWeek),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = frakt_loan_summary(Some(request), &api_key).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: FraktLoanSummaryResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
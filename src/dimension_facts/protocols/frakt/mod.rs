@@ -0,0 +1,3 @@
+pub mod bonds_loan_events;
+pub mod loan_events;
+pub mod loan_summary;
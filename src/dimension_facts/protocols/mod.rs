@@ -0,0 +1,25 @@
+pub mod citrus;
+pub mod foxy;
+pub mod frakt;
+pub mod sharky;
+
+use serde::{Serialize, Serializer};
+
+/// The action types of the protocol events, whose `Other` variant catches the instructions
+/// unknown to this crate when reading events.
+pub(crate) trait ActionType: Serialize + PartialEq {
+    const OTHER: Self;
+}
+
+/// Serialize the action filter of a request, failing on `Other` which the api does not know.
+pub(crate) fn serialize_action_filter<A: ActionType, S: Serializer>(
+    value: &Option<A>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if value.as_ref() == Some(&A::OTHER) {
+        return Err(serde::ser::Error::custom(
+            "Other can not be used as an action filter",
+        ));
+    }
+    value.serialize(serializer)
+}
@@ -0,0 +1,107 @@
+//! # Sharky APY
+//!
+//! POST `https://rest-api.hellomoon.io/v0/sharky/apy`
+//!
+//! The annual percentage yield and loan duration of every Sharky order book, one per collection.
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SharkyApyRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The address of the order book of the collection
+    #[serde(rename = "orderBook")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub order_book: String,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SharkyApyResponse {
+    /// array of objects
+    pub data: Option<Vec<SharkyApy>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SharkyApy {
+    /// The address of the order book of the collection
+    #[serde(rename = "orderBook")]
+    pub order_book: Option<String>,
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// Name of the collection
+    #[serde(rename = "collectionName")]
+    pub collection_name: Option<String>,
+    /// Annual percentage yield paid to the lenders, in percent
+    pub apy: Option<f64>,
+    /// Duration of the loans, in seconds
+    #[serde(rename = "durationSeconds")]
+    pub duration_seconds: Option<u64>,
+}
+
+impl SharkyApy {
+    /// Annual percentage rate matching the yield compounded over each loan duration, in percent.
+    pub fn apr(&self) -> Option<f64> {
+        apy_to_apr(self.apy?, self.duration_seconds?)
+    }
+}
+
+/// Annual percentage rate, in percent, of a yield compounded every `duration_seconds`.
+pub fn apy_to_apr(apy: f64, duration_seconds: u64) -> Option<f64> {
+    if duration_seconds == 0 {
+        return None;
+    }
+    let periods = 365.0 * 86400.0 / duration_seconds as f64;
+    Some(((1.0 + apy / 100.0).powf(1.0 / periods) - 1.0) * periods * 100.0)
+}
+
+pub async fn sharky_apy(
+    request: Option<SharkyApyRequest>,
+    api_key: &str,
+) -> anyhow::Result<SharkyApyResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/sharky/apy");
+    core_call::<SharkyApyRequest, SharkyApyResponse>(request, api_url, api_key).await
+}
+
+#[test]
+fn test_apy_to_apr() {
+    // A loan over the whole year compounds once, the rate is the yield.
+    assert!((apy_to_apr(150.0, 365 * 86400).unwrap() - 150.0).abs() < 1e-9);
+    let apr = apy_to_apr(150.0, 7 * 86400).unwrap();
+    assert!(apr > 90.0 && apr < 95.0);
+    assert_eq!(apy_to_apr(150.0, 0), None);
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_sharky_apy() {
+    let request = SharkyApyRequest::default();
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = sharky_apy(Some(request), &api_key).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: SharkyApyResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
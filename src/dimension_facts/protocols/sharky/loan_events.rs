@@ -0,0 +1,189 @@
+//! # Sharky Loan Events
+//!
+//! POST `https://rest-api.hellomoon.io/v0/sharky/loan-events`
+//!
+//! Every instruction of the Sharky NFT lending program: lenders offer loans on a collection's
+//! order book, borrowers take them by escrowing an NFT, and the loan is repaid or foreclosed by
+//! the lender once its duration is over. Amounts are in lamports.
+use crate::dimension_facts::protocols::{serialize_action_filter, ActionType};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SharkyLoanEventsRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The address of the loan account
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub loan: String,
+    /// The address of the order book of the collection
+    #[serde(rename = "orderBook")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub order_book: String,
+    /// PublicKey of the lender
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub lender: String,
+    /// PublicKey of the borrower
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub borrower: String,
+    /// The mint address of the NFT escrowed as collateral
+    #[serde(rename = "collateralMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub collateral_mint: String,
+    #[serde(rename = "actionType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_action_filter")]
+    pub action_type: Option<SharkyLoanAction>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+/// The step of the loan an event is about.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum SharkyLoanAction {
+    /// A lender offered a loan on the order book
+    #[serde(rename = "offerLoan")]
+    OfferLoan,
+    /// A lender took back an offer that was not taken
+    #[serde(rename = "rescindLoan")]
+    RescindLoan,
+    /// A borrower took an offer and escrowed the collateral
+    #[serde(rename = "takeLoan")]
+    TakeLoan,
+    /// The borrower repaid the loan and got the collateral back
+    #[serde(rename = "repayLoan")]
+    RepayLoan,
+    /// The lender took the collateral of an overdue loan
+    #[serde(rename = "forecloseLoan")]
+    ForecloseLoan,
+    /// The borrower repaid the loan with a new one from another lender
+    #[serde(rename = "extendLoan")]
+    ExtendLoan,
+    /// An instruction not listed above
+    #[serde(other)]
+    Other,
+}
+
+impl ActionType for SharkyLoanAction {
+    const OTHER: Self = SharkyLoanAction::Other;
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SharkyLoanEventsResponse {
+    /// array of objects
+    pub data: Option<Vec<SharkyLoanEvent>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SharkyLoanEvent {
+    /// Numeric identifier of a block describing the slot that the block was produced in
+    #[serde(rename = "blockId")]
+    pub block_id: Option<usize>,
+    /// Unix epoch time (in seconds) of a block as calculated from validator votes.
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<usize>,
+    /// First signature in a transaction, which can be used to track and verify the transaction status across the complete ledger.
+    /// It is a base-58 encoded string that is uniquely generated for each transaction.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: Option<String>,
+    #[serde(rename = "actionType")]
+    pub action_type: Option<SharkyLoanAction>,
+    /// The address of the loan account
+    pub loan: Option<String>,
+    /// The address of the order book of the collection
+    #[serde(rename = "orderBook")]
+    pub order_book: Option<String>,
+    /// PublicKey of the lender
+    pub lender: Option<String>,
+    /// PublicKey of the borrower, empty while the offer is not taken
+    pub borrower: Option<String>,
+    /// The mint address of the NFT escrowed as collateral
+    #[serde(rename = "collateralMint")]
+    pub collateral_mint: Option<String>,
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// Amount lent, in lamports
+    #[serde(rename = "principalLamports")]
+    pub principal_lamports: Option<u64>,
+    /// Annual percentage yield of the order book, in percent
+    pub apy: Option<f64>,
+    /// Duration of the loan, in seconds
+    #[serde(rename = "durationSeconds")]
+    pub duration_seconds: Option<u64>,
+}
+
+impl SharkyLoanEvent {
+    pub fn principal_sol(&self) -> Option<f64> {
+        self.principal_lamports.map(lamports_to_sol)
+    }
+}
+
+pub async fn sharky_loan_events(
+    request: Option<SharkyLoanEventsRequest>,
+    api_key: &str,
+) -> anyhow::Result<SharkyLoanEventsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/sharky/loan-events");
+    core_call::<SharkyLoanEventsRequest, SharkyLoanEventsResponse>(request, api_url, api_key).await
+}
+
+#[test]
+fn test_sharky_loan_event_serde() {
+    let event: SharkyLoanEvent = serde_json::from_str(
+        r#"{"actionType": "forecloseLoan", "principalLamports": 500000000, "apy": 140.5, "durationSeconds": 1209600}"#,
+    )
+    .unwrap();
+    assert_eq!(event.action_type, Some(SharkyLoanAction::ForecloseLoan));
+    assert_eq!(event.principal_sol(), Some(0.5));
+    assert_eq!(event.duration_seconds, Some(86400 * 14));
+
+    let request = SharkyLoanEventsRequest {
+        action_type: Some(SharkyLoanAction::ForecloseLoan),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({ "actionType": "forecloseLoan" })
+    );
+
+    let request = SharkyLoanEventsRequest {
+        action_type: Some(SharkyLoanAction::Other),
+        ..Default::default()
+    };
+    assert!(serde_json::to_string(&request).is_err());
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_sharky_loan_events() {
+    let request = SharkyLoanEventsRequest {
+        action_type: Some(SharkyLoanAction::TakeLoan),
+        limit: 10,
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = sharky_loan_events(Some(request), &api_key).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: SharkyLoanEventsResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
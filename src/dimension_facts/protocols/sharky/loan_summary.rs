@@ -0,0 +1,139 @@
+//! # Sharky Loan Summary
+//!
+//! POST `https://rest-api.hellomoon.io/v0/sharky/loan-summary`
+//!
+//! Sharky loans of a collection per day, week or month: offers, loans taken, repaid and
+//! foreclosed, and the amount lent. Amounts are in lamports.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SharkyLoanSummaryRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the loans are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SharkyLoanSummaryResponse {
+    /// array of objects
+    pub data: Option<Vec<SharkyLoanSummary>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SharkyLoanSummary {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of loans offered
+    #[serde(rename = "loansOffered")]
+    pub loans_offered: Option<u64>,
+    /// Number of loans taken
+    #[serde(rename = "loansTaken")]
+    pub loans_taken: Option<u64>,
+    /// Number of loans repaid, extensions included
+    #[serde(rename = "loansRepaid")]
+    pub loans_repaid: Option<u64>,
+    /// Number of loans foreclosed
+    #[serde(rename = "loansForeclosed")]
+    pub loans_foreclosed: Option<u64>,
+    /// Amount lent, in lamports
+    pub volume: Option<u64>,
+    /// Average annual percentage yield of the loans taken, in percent
+    #[serde(rename = "averageApy")]
+    pub average_apy: Option<f64>,
+}
+
+impl SharkyLoanSummary {
+    pub fn volume_sol(&self) -> Option<f64> {
+        self.volume.map(lamports_to_sol)
+    }
+
+    /// Share of the loans closed in the period that were foreclosed, between 0 and 1.
+    pub fn foreclosure_rate(&self) -> Option<f64> {
+        let foreclosed = self.loans_foreclosed?;
+        let closed = foreclosed + self.loans_repaid?;
+        (closed > 0).then(|| foreclosed as f64 / closed as f64)
+    }
+}
+
+pub async fn sharky_loan_summary(
+    request: Option<SharkyLoanSummaryRequest>,
+    api_key: &str,
+) -> anyhow::Result<SharkyLoanSummaryResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/sharky/loan-summary");
+    core_call::<SharkyLoanSummaryRequest, SharkyLoanSummaryResponse>(request, api_url, api_key)
+        .await
+}
+
+/// Sharky loans of a collection per period matching `day`.
+pub async fn sharky_loan_summary_time_series(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<SharkyLoanSummary>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = SharkyLoanSummaryRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = sharky_loan_summary(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(TimeSeries::from_rows(granularity, rows, |row| {
+        Some((row.day.clone()?, row))
+    }))
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_sharky_loan_summary() {
+    let request = SharkyLoanSummaryRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        day: Some(DateRange::since("2023-01-01")),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = sharky_loan_summary(Some(request), &api_key).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: SharkyLoanSummaryResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
@@ -0,0 +1,300 @@
+//! # Stream events
+//!
+//! The rows delivered by a subscription, over a websocket or to a webhook.
+//!
+//! Every message carries the id of the subscription it belongs to and a sequence number
+//! incremented by one per message of the subscription, so missed messages can be detected.
+//! The row itself is tagged by `type` with its content in `data`:
+//!
+//! ```json
+//! {
+//!     "subscriptionId": "...",
+//!     "sequence": 42,
+//!     "type": "DexTrade",
+//!     "data": { "transactionId": "...", "programId": "...", ... }
+//! }
+//! ```
+//!
+//! Messages may be delivered one by one or batched in a json array.
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct StreamMessage {
+    /// Id of the subscription the message was delivered for
+    #[serde(rename = "subscriptionId")]
+    pub subscription_id: String,
+    /// Incremented by one per message of the subscription
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u64>,
+    #[serde(flatten)]
+    pub event: StreamEvent,
+}
+
+impl StreamMessage {
+    /// Parse a websocket frame or webhook body holding a single message or a batch of them.
+    pub fn parse_batch(payload: &str) -> serde_json::Result<Vec<StreamMessage>> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Batch {
+            One(Box<StreamMessage>),
+            Many(Vec<StreamMessage>),
+        }
+
+        Ok(match serde_json::from_str(payload)? {
+            Batch::One(message) => vec![*message],
+            Batch::Many(messages) => messages,
+        })
+    }
+
+    /// Parse the rows of a frame holding a single message or a batch of them one at a time, so
+    /// a malformed row does not drop the others of its batch.
+    ///
+    /// `None` when the payload is not a json object nor array, e.g. a plain text acknowledgement.
+    pub fn parse_rows(payload: &str) -> Option<Vec<Result<StreamMessage, MalformedRow>>> {
+        let rows = match serde_json::from_str(payload).ok()? {
+            serde_json::Value::Array(rows) => rows,
+            row @ serde_json::Value::Object(_) => vec![row],
+            _ => return None,
+        };
+        let parse_row = |row: serde_json::Value| {
+            let sequence = row.get("sequence").and_then(serde_json::Value::as_u64);
+            serde_json::from_value(row).map_err(|error| MalformedRow { sequence, error })
+        };
+        Some(rows.into_iter().map(parse_row).collect())
+    }
+}
+
+/// A row that failed to decode, with its sequence number when it could be read.
+#[derive(Debug)]
+pub struct MalformedRow {
+    pub sequence: Option<u64>,
+    pub error: serde_json::Error,
+}
+
+impl std::fmt::Display for MalformedRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.sequence {
+            Some(sequence) => write!(f, "message {}: {}", sequence, self.error),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl std::error::Error for MalformedRow {}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(try_from = "RawStreamEvent", into = "RawStreamEvent")]
+pub enum StreamEvent {
+    ParsedAccountUpdate(ParsedAccountUpdate),
+    UnparsedAccountUpdate(UnparsedAccountUpdate),
+    UnparsedTransaction(UnparsedTransaction),
+    DexTrade(DexTrade),
+    /// A row of a datastream this crate does not know yet
+    Unknown {
+        event_type: String,
+        data: serde_json::Value,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawStreamEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+impl TryFrom<RawStreamEvent> for StreamEvent {
+    type Error = serde_json::Error;
+
+    fn try_from(raw: RawStreamEvent) -> Result<Self, Self::Error> {
+        Ok(match raw.event_type.as_str() {
+            "ParsedAccountUpdate" => Self::ParsedAccountUpdate(serde_json::from_value(raw.data)?),
+            "UnparsedAccountUpdate" => {
+                Self::UnparsedAccountUpdate(serde_json::from_value(raw.data)?)
+            }
+            "UnparsedTransaction" => Self::UnparsedTransaction(serde_json::from_value(raw.data)?),
+            "DexTrade" => Self::DexTrade(serde_json::from_value(raw.data)?),
+            _ => Self::Unknown {
+                event_type: raw.event_type,
+                data: raw.data,
+            },
+        })
+    }
+}
+
+impl From<StreamEvent> for RawStreamEvent {
+    fn from(event: StreamEvent) -> Self {
+        let (event_type, data) = match event {
+            StreamEvent::ParsedAccountUpdate(data) => {
+                ("ParsedAccountUpdate", serde_json::to_value(data))
+            }
+            StreamEvent::UnparsedAccountUpdate(data) => {
+                ("UnparsedAccountUpdate", serde_json::to_value(data))
+            }
+            StreamEvent::UnparsedTransaction(data) => {
+                ("UnparsedTransaction", serde_json::to_value(data))
+            }
+            StreamEvent::DexTrade(data) => ("DexTrade", serde_json::to_value(data)),
+            StreamEvent::Unknown { event_type, data } => return Self { event_type, data },
+        };
+        Self {
+            event_type: event_type.to_string(),
+            data: data.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct ParsedAccountUpdate {
+    /// Public key of the updated account
+    pub account: String,
+    /// The program owning the account
+    #[serde(rename = "programId")]
+    pub program_id: String,
+    /// The parsed type of the account — e.g. `TokenAccount`
+    #[serde(rename = "accountType")]
+    pub account_type: Option<String>,
+    /// The slot the update happened in
+    pub slot: u64,
+    /// The account data decoded into named fields
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct UnparsedAccountUpdate {
+    /// Public key of the updated account
+    pub account: String,
+    /// The program owning the account
+    pub owner: String,
+    /// Balance of the account, in lamports
+    pub lamports: u64,
+    /// The base64 encoded account data
+    pub data: String,
+    /// Whether the account holds a program
+    #[serde(default)]
+    pub executable: bool,
+    /// The slot the update happened in
+    pub slot: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct UnparsedTransaction {
+    /// First signature in a transaction, which can be used to track and verify the transaction status across the complete ledger.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: String,
+    /// The slot the transaction was processed in
+    pub slot: u64,
+    /// Unix epoch time (in seconds) of the block
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<i64>,
+    /// The base64 encoded transaction
+    pub transaction: String,
+    /// The transaction status meta, as returned by the getTransaction RPC method
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct DexTrade {
+    /// First signature in a transaction, which can be used to track and verify the transaction status across the complete ledger.
+    #[serde(rename = "transactionId")]
+    pub transaction_id: String,
+    /// The slot the trade was processed in
+    pub slot: u64,
+    /// Unix epoch time (in seconds) of the block
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<i64>,
+    /// The DEX program the trade was made on
+    #[serde(rename = "programId")]
+    pub program_id: String,
+    /// The user that made the trade
+    #[serde(rename = "userAccount")]
+    pub user_account: String,
+    /// Mint of the token sent to the DEX
+    #[serde(rename = "sourceMint")]
+    pub source_mint: String,
+    /// Amount of the token sent, unconverted for decimals
+    #[serde(rename = "sourceAmount")]
+    pub source_amount: u64,
+    /// Mint of the token received from the DEX
+    #[serde(rename = "destinationMint")]
+    pub destination_mint: String,
+    /// Amount of the token received, unconverted for decimals
+    #[serde(rename = "destinationAmount")]
+    pub destination_amount: u64,
+    /// The aggregator that routed the trade — e.g. `Jupiter v4`
+    #[serde(rename = "aggregatorName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregator_name: Option<String>,
+}
+
+#[test]
+fn test_stream_message_parse_batch() {
+    let one = r#"{
+        "subscriptionId": "sub",
+        "sequence": 1,
+        "type": "DexTrade",
+        "data": {
+            "transactionId": "tx",
+            "slot": 10,
+            "blockTime": 1673831466,
+            "programId": "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
+            "userAccount": "user",
+            "sourceMint": "So11111111111111111111111111111111111111112",
+            "sourceAmount": 1000000000,
+            "destinationMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "destinationAmount": 20000000
+        }
+    }"#;
+    let messages = StreamMessage::parse_batch(one).unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].sequence, Some(1));
+    let StreamEvent::DexTrade(trade) = &messages[0].event else {
+        panic!("unexpected event: {:?}", messages[0].event);
+    };
+    assert_eq!(trade.destination_amount, 20_000_000);
+
+    let many = r#"[
+        { "subscriptionId": "sub", "sequence": 2, "type": "SomethingNew", "data": {} },
+        { "subscriptionId": "sub", "type": "UnparsedAccountUpdate",
+          "data": { "account": "a", "owner": "o", "lamports": 1, "data": "AA==", "slot": 3 } }
+    ]"#;
+    let messages = StreamMessage::parse_batch(many).unwrap();
+    assert_eq!(
+        messages[0].event,
+        StreamEvent::Unknown {
+            event_type: "SomethingNew".to_string(),
+            data: serde_json::json!({}),
+        }
+    );
+    let json = serde_json::to_value(&messages[1]).unwrap();
+    assert_eq!(json["type"], "UnparsedAccountUpdate");
+    assert_eq!(json["data"]["lamports"], 1);
+    assert!(matches!(
+        messages[1].event,
+        StreamEvent::UnparsedAccountUpdate(_)
+    ));
+}
+
+#[test]
+fn test_stream_message_parse_rows() {
+    let rows = StreamMessage::parse_rows(
+        r#"[
+            { "subscriptionId": "sub", "sequence": 1, "type": "SomethingNew", "data": {} },
+            { "subscriptionId": "sub", "sequence": 2, "type": "DexTrade", "data": { "slot": "x" } },
+            { "subscriptionId": "sub", "sequence": 3, "type": "SomethingNew", "data": {} }
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0].as_ref().unwrap().sequence, Some(1));
+    assert_eq!(rows[1].as_ref().unwrap_err().sequence, Some(2));
+    assert_eq!(rows[2].as_ref().unwrap().sequence, Some(3));
+
+    let rows = StreamMessage::parse_rows(r#"{ "error": "invalid api key" }"#).unwrap();
+    assert_eq!(rows[0].as_ref().unwrap_err().sequence, None);
+
+    assert!(StreamMessage::parse_rows("You have successfully subscribed").is_none());
+    assert!(StreamMessage::parse_rows("42").is_none());
+}
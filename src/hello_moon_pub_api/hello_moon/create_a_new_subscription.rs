@@ -1,45 +1,45 @@
-use serde::{Deserialize, Serialize};
-
-use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
-
-#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
-    #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
-    #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
-    #[serde(rename = "paginationToken")]
-    #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
-    /// array of objects
-    data: Option<Vec<IResponse>>,
-    #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+//! # Create a new subscription
+//!
+//! POST `https://rest-api.hellomoon.io/v1/subscriptions`
+//!
+//! Create a subscription to a Hello Moon datastream, delivering the rows matching its filters
+//! to a webhook or over a websocket.
+use super::subscription::{Subscription, SubscriptionSpec, SUBSCRIPTION_API_URL};
+use crate::core_call;
+
+pub async fn create_subscription(
+    api_key: &str,
+    request: SubscriptionSpec,
+) -> anyhow::Result<Subscription> {
+    core_call::<SubscriptionSpec, Subscription>(
+        Some(request),
+        SUBSCRIPTION_API_URL.to_string(),
+        api_key,
+    )
+    .await
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_create_subscription() {
+    use super::delete_a_subscription::delete_subscription;
+    use super::subscription::{DeliveryTarget, FilterCondition, SubscriptionFilter};
+
+    let request = SubscriptionSpec {
+        name: "hellomoon-rs test".to_string(),
+        data_stream_id: dotenv::var("data_stream_id").unwrap(),
+        filters: SubscriptionFilter::default().with(
+            "helloMoonCollectionId",
+            FilterCondition::equal("040de757c0d2b75dcee999ddd47689c4"),
+        ),
+        delivery: DeliveryTarget::Websocket,
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
+    let subscription = create_subscription(&api_key, request).await.unwrap();
+    println!("{:#?}", subscription);
 
-    let left = example(Some(request), &api_key).await.unwrap();
-
-    let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
-    println!("{:#?}", right);
-    assert_eq!(left, right);
+    delete_subscription(&api_key, &subscription.subscription_id)
+        .await
+        .unwrap();
 }
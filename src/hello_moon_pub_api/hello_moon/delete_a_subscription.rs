@@ -0,0 +1,29 @@
+//! # Delete a subscription
+//!
+//! DELETE `https://rest-api.hellomoon.io/v1/subscriptions/{subscriptionId}`
+//!
+//! Delete a subscription, its rows stop being delivered.
+use super::subscription::subscription_api_url;
+use crate::core_call_with_method;
+
+pub async fn delete_subscription(api_key: &str, subscription_id: &str) -> anyhow::Result<()> {
+    core_call_with_method::<(), serde_json::Value>(
+        reqwest::Method::DELETE,
+        None,
+        subscription_api_url(subscription_id),
+        api_key,
+    )
+    .await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_delete_subscription() {
+    let api_key = dotenv::var("api_keys").unwrap();
+    let subscription_id = dotenv::var("subscription_id").unwrap();
+
+    delete_subscription(&api_key, &subscription_id)
+        .await
+        .unwrap();
+}
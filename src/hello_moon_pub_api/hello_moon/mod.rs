@@ -1,5 +1,8 @@
 pub mod create_a_new_subscription;
-pub mod delate_a_subscription;
+pub mod delete_a_subscription;
 pub mod parsed_dexes;
+pub mod reconcile;
+pub mod retrieve_a_subscription;
 pub mod retrieve_the_list_of_stream_in_your_account;
+pub mod subscription;
 pub mod update_a_subscription;
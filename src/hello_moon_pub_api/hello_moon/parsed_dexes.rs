@@ -1,45 +1,196 @@
-use serde::{Deserialize, Serialize};
+//! # Parsed Dexes
+//!
+//! POST `https://rest-api.hellomoon.io/v0/hello-moon/parsed-dexes`
+//!
+//! The DEX programs Hello Moon parses swaps and liquidity pool events of, with the instruction
+//! types supported per program.
+//!
+//! The list rarely changes, so [`dex_registry`] loads it once per process into a [`DexRegistry`]
+//! which resolves the program ids of other endpoints' rows — e.g. DeFi Swaps — to DEX names.
+use std::collections::HashMap;
 
+use crate::fetch_all_pages;
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
+use tokio::sync::OnceCell;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct ParsedDexesRequest {
+    /// The public key (address) of the account containing the program on chain.
+    #[serde(rename = "programId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub program_id: String,
+    /// Name of the DEX — e.g. `Orca Whirlpool`
+    #[serde(rename = "programName")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub program_name: String,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct ParsedDexesResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<ParsedDex>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct ParsedDex {
+    /// Name of the DEX — e.g. `Orca Whirlpool`
+    #[serde(rename = "programName")]
+    pub program_name: Option<String>,
+    /// The public key (address) of the account containing the program on chain.
+    #[serde(rename = "programId")]
+    pub program_id: Option<String>,
+    /// The instructions of the program Hello Moon parses — e.g. `swap`, `addLiquidity`
+    #[serde(rename = "instructionTypes")]
+    #[serde(default)]
+    pub instruction_types: Vec<String>,
+}
+
+pub async fn parsed_dexes(
+    request: Option<ParsedDexesRequest>,
+    api_key: &str,
+) -> anyhow::Result<ParsedDexesResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/hello-moon/parsed-dexes");
+    core_call::<ParsedDexesRequest, ParsedDexesResponse>(request, api_url, api_key).await
+}
+
+/// The parsed DEX programs, indexed by program id and name.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct DexRegistry {
+    by_program_id: HashMap<String, ParsedDex>,
+    by_name: HashMap<String, String>,
+}
+
+impl DexRegistry {
+    /// Dexes without a program id are ignored.
+    pub fn new(dexes: impl IntoIterator<Item = ParsedDex>) -> Self {
+        let mut registry = Self::default();
+        for dex in dexes {
+            let Some(program_id) = dex.program_id.clone() else {
+                continue;
+            };
+            if let Some(name) = dex.program_name.as_deref() {
+                registry
+                    .by_name
+                    .insert(name.to_lowercase(), program_id.clone());
+            }
+            registry.by_program_id.insert(program_id, dex);
+        }
+        registry
+    }
+
+    /// Page through the Parsed Dexes endpoint.
+    pub async fn load(api_key: &str) -> anyhow::Result<Self> {
+        let dexes = fetch_all_pages(|pagination_token| {
+            let request = ParsedDexesRequest {
+                pagination_token,
+                ..Default::default()
+            };
+            async move {
+                let response = parsed_dexes(Some(request), api_key).await?;
+                Ok((response.data, response.pagination_token))
+            }
+        })
+        .await?;
+        Ok(Self::new(dexes))
+    }
+
+    pub fn get(&self, program_id: &str) -> Option<&ParsedDex> {
+        self.by_program_id.get(program_id)
+    }
+
+    /// Name of the DEX of `program_id`, `None` when Hello Moon does not parse the program.
+    pub fn name(&self, program_id: &str) -> Option<&str> {
+        self.get(program_id)?.program_name.as_deref()
+    }
+
+    /// Program id of the DEX named `name`, case insensitive.
+    pub fn program_id(&self, name: &str) -> Option<&str> {
+        self.by_name.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    pub fn supports(&self, program_id: &str, instruction_type: &str) -> bool {
+        self.get(program_id).is_some_and(|dex| {
+            dex.instruction_types
+                .iter()
+                .any(|supported| supported == instruction_type)
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_program_id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_program_id.is_empty()
+    }
+}
+
+static DEX_REGISTRY: OnceCell<DexRegistry> = OnceCell::const_new();
+
+/// The registry shared by the process, loaded on the first call.
+///
+/// A failed load is not cached, the next call tries again.
+pub async fn dex_registry(api_key: &str) -> anyhow::Result<&'static DexRegistry> {
+    DEX_REGISTRY
+        .get_or_try_init(|| DexRegistry::load(api_key))
+        .await
+}
+
+#[test]
+fn test_dex_registry() {
+    let registry = DexRegistry::new(vec![
+        ParsedDex {
+            program_name: Some("Orca Whirlpool".to_string()),
+            program_id: Some("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string()),
+            instruction_types: vec!["swap".to_string(), "increaseLiquidity".to_string()],
+        },
+        ParsedDex {
+            program_name: Some("Unknown".to_string()),
+            program_id: None,
+            ..Default::default()
+        },
+    ]);
+
+    assert_eq!(registry.len(), 1);
+    assert_eq!(
+        registry.name("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"),
+        Some("Orca Whirlpool")
+    );
+    assert_eq!(
+        registry.program_id("orca whirlpool"),
+        Some("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc")
+    );
+    assert!(registry.supports("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc", "swap"));
+    assert!(!registry.supports("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc", "deposit"));
+    assert_eq!(registry.name("unknown"), None);
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_parsed_dexes() {
+    let request = ParsedDexesRequest::default();
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = parsed_dexes(Some(request), &api_key).await.unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: ParsedDexesResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -0,0 +1,431 @@
+//! # Reconcile subscriptions
+//!
+//! Bring the subscriptions of an account to a desired set of [`SubscriptionSpec`]s, the way
+//! `terraform plan` and `terraform apply` do.
+//!
+//! Subscriptions are matched on their name, so the desired set can be kept as versioned json or
+//! toml config per environment. Comparing it to the subscriptions listed by the api gives a
+//! [`ReconcilePlan`]:
+//!
+//! - a desired spec without a subscription of that name is created
+//! - a subscription whose filters or delivery differ from its spec is updated
+//! - a subscription whose datastream differs from its spec is replaced, created then deleted so a
+//!   failed creation leaves the old stream in place
+//! - a subscription without a desired spec is deleted, as are duplicates of a name
+//!
+//! Only the subscriptions managed by the reconciliation are touched, see [`ManagedSubscriptions`].
+//!
+//! The plan displays as a diff, which [`reconcile_subscriptions`] prints instead of applying it
+//! in dry-run mode.
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use super::create_a_new_subscription::create_subscription;
+use super::delete_a_subscription::delete_subscription;
+use super::retrieve_the_list_of_stream_in_your_account::list_subscriptions;
+use super::subscription::{Subscription, SubscriptionSpec};
+use super::update_a_subscription::update_subscription;
+use crate::fetch_all_pages;
+
+/// The existing subscriptions a reconciliation may update or delete.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ManagedSubscriptions {
+    /// Only the subscriptions whose name starts with the prefix, e.g. `prod/`. The names of the
+    /// desired specs must start with it too.
+    NamePrefix(String),
+    /// Every subscription of the account, the ones without a desired spec are deleted
+    All,
+}
+
+impl ManagedSubscriptions {
+    pub fn manages(&self, name: &str) -> bool {
+        match self {
+            ManagedSubscriptions::NamePrefix(prefix) => name.starts_with(prefix.as_str()),
+            ManagedSubscriptions::All => true,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ReconcileAction {
+    Create(SubscriptionSpec),
+    Update {
+        subscription_id: String,
+        current: SubscriptionSpec,
+        desired: SubscriptionSpec,
+    },
+    /// Create `desired`, then delete `current` whose datastream differs
+    Replace {
+        current: Subscription,
+        desired: SubscriptionSpec,
+    },
+    Delete(Subscription),
+}
+
+/// The actions bringing the existing subscriptions to the desired ones: deletions, updates,
+/// creations then replacements.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ReconcilePlan {
+    pub actions: Vec<ReconcileAction>,
+}
+
+impl ReconcilePlan {
+    /// Compare the `desired` specs to the `managed` ones of the `existing` subscriptions.
+    ///
+    /// Fails when two desired specs share a name or a desired spec is not managed.
+    pub fn new(
+        desired: &[SubscriptionSpec],
+        existing: &[Subscription],
+        managed: &ManagedSubscriptions,
+    ) -> anyhow::Result<Self> {
+        let mut names = HashSet::new();
+        for spec in desired {
+            if !managed.manages(&spec.name) {
+                anyhow::bail!("desired subscription is not managed: {}", spec.name);
+            }
+            if !names.insert(spec.name.as_str()) {
+                anyhow::bail!(
+                    "duplicate subscription name in desired specs: {}",
+                    spec.name
+                );
+            }
+        }
+
+        let mut by_name: BTreeMap<&str, Vec<&Subscription>> = BTreeMap::new();
+        for subscription in existing
+            .iter()
+            .filter(|subscription| managed.manages(&subscription.spec.name))
+        {
+            by_name
+                .entry(subscription.spec.name.as_str())
+                .or_default()
+                .push(subscription);
+        }
+
+        let mut deletes = vec![];
+        let mut updates = vec![];
+        let mut creates = vec![];
+        let mut replaces = vec![];
+        for spec in desired {
+            let mut current = by_name.remove(spec.name.as_str()).unwrap_or_default();
+            if current.is_empty() {
+                creates.push(ReconcileAction::Create(spec.clone()));
+                continue;
+            }
+            let kept = current.remove(0);
+            deletes.extend(
+                current
+                    .into_iter()
+                    .map(|subscription| ReconcileAction::Delete(subscription.clone())),
+            );
+            if kept.spec.data_stream_id != spec.data_stream_id {
+                replaces.push(ReconcileAction::Replace {
+                    current: kept.clone(),
+                    desired: spec.clone(),
+                });
+            } else if kept.spec != *spec {
+                updates.push(ReconcileAction::Update {
+                    subscription_id: kept.subscription_id.clone(),
+                    current: kept.spec.clone(),
+                    desired: spec.clone(),
+                });
+            }
+        }
+        deletes.extend(
+            by_name
+                .into_values()
+                .flatten()
+                .map(|subscription| ReconcileAction::Delete(subscription.clone())),
+        );
+
+        Ok(Self {
+            actions: deletes
+                .into_iter()
+                .chain(updates)
+                .chain(creates)
+                .chain(replaces)
+                .collect(),
+        })
+    }
+
+    /// Whether the existing subscriptions already match the desired ones.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Run the actions in order, stopping at the first failure.
+    pub async fn apply(&self, api_key: &str) -> anyhow::Result<()> {
+        for action in &self.actions {
+            match action {
+                ReconcileAction::Create(spec) => {
+                    let subscription = create_subscription(api_key, spec.clone()).await?;
+                    log::info!(
+                        "created subscription {} ({})",
+                        spec.name,
+                        subscription.subscription_id
+                    );
+                }
+                ReconcileAction::Update {
+                    subscription_id,
+                    desired,
+                    ..
+                } => {
+                    update_subscription(api_key, subscription_id, desired.clone()).await?;
+                    log::info!(
+                        "updated subscription {} ({})",
+                        desired.name,
+                        subscription_id
+                    );
+                }
+                ReconcileAction::Replace { current, desired } => {
+                    let subscription = create_subscription(api_key, desired.clone()).await?;
+                    delete_subscription(api_key, &current.subscription_id).await?;
+                    log::info!(
+                        "replaced subscription {} ({} -> {})",
+                        desired.name,
+                        current.subscription_id,
+                        subscription.subscription_id
+                    );
+                }
+                ReconcileAction::Delete(subscription) => {
+                    delete_subscription(api_key, &subscription.subscription_id).await?;
+                    log::info!(
+                        "deleted subscription {} ({})",
+                        subscription.spec.name,
+                        subscription.subscription_id
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+impl fmt::Display for ReconcilePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (mut creates, mut updates, mut replaces, mut deletes) = (0, 0, 0, 0);
+        for action in &self.actions {
+            match action {
+                ReconcileAction::Create(spec) => {
+                    creates += 1;
+                    writeln!(f, "+ {} ({})", spec.name, spec.data_stream_id)?;
+                    writeln!(f, "    filters: {}", to_json(&spec.filters))?;
+                    writeln!(f, "    delivery: {}", to_json(&spec.delivery))?;
+                }
+                ReconcileAction::Update {
+                    subscription_id,
+                    current,
+                    desired,
+                } => {
+                    updates += 1;
+                    writeln!(f, "~ {} ({})", desired.name, subscription_id)?;
+                    if current.filters != desired.filters {
+                        writeln!(
+                            f,
+                            "    filters: {} -> {}",
+                            to_json(&current.filters),
+                            to_json(&desired.filters)
+                        )?;
+                    }
+                    if current.delivery != desired.delivery {
+                        writeln!(
+                            f,
+                            "    delivery: {} -> {}",
+                            to_json(&current.delivery),
+                            to_json(&desired.delivery)
+                        )?;
+                    }
+                }
+                ReconcileAction::Replace { current, desired } => {
+                    replaces += 1;
+                    writeln!(f, "-/+ {} ({})", desired.name, current.subscription_id)?;
+                    writeln!(
+                        f,
+                        "    dataStreamId: {} -> {}",
+                        current.spec.data_stream_id, desired.data_stream_id
+                    )?;
+                }
+                ReconcileAction::Delete(subscription) => {
+                    deletes += 1;
+                    writeln!(
+                        f,
+                        "- {} ({})",
+                        subscription.spec.name, subscription.subscription_id
+                    )?;
+                }
+            }
+        }
+        write!(
+            f,
+            "Plan: {} to create, {} to update, {} to replace, {} to delete.",
+            creates, updates, replaces, deletes
+        )
+    }
+}
+
+/// List every subscription of the account and bring the `managed` ones to `desired`.
+///
+/// With `dry_run` the plan is printed and nothing is changed.
+pub async fn reconcile_subscriptions(
+    api_key: &str,
+    desired: &[SubscriptionSpec],
+    managed: &ManagedSubscriptions,
+    dry_run: bool,
+) -> anyhow::Result<ReconcilePlan> {
+    let existing = fetch_all_pages(|pagination_token| async move {
+        let response = list_subscriptions(api_key, &pagination_token).await?;
+        Ok((response.data, response.pagination_token))
+    })
+    .await?;
+    let plan = ReconcilePlan::new(desired, &existing, managed)?;
+    if dry_run {
+        println!("{}", plan);
+    } else {
+        plan.apply(api_key).await?;
+    }
+    Ok(plan)
+}
+
+#[test]
+fn test_reconcile_plan() {
+    use super::subscription::{DeliveryTarget, FilterCondition, SubscriptionFilter};
+
+    #[derive(serde::Deserialize)]
+    struct Config {
+        subscriptions: Vec<SubscriptionSpec>,
+    }
+    let config: Config = toml::from_str(
+        r#"
+        [[subscriptions]]
+        name = "okay bears sales"
+        dataStreamId = "nft-secondary-sales"
+        delivery = { type = "webhook", url = "https://example.com/hook" }
+        filters.helloMoonCollectionId = { operator = "=", value = "040de757c0d2b75dcee999ddd47689c4" }
+
+        [[subscriptions]]
+        name = "jupiter swaps"
+        dataStreamId = "dex-trades"
+        delivery = { type = "websocket" }
+
+        [[subscriptions]]
+        name = "sol transfers"
+        dataStreamId = "token-transfers"
+        delivery = { type = "websocket" }
+        "#,
+    )
+    .unwrap();
+    let desired = config.subscriptions;
+
+    let subscription = |id: &str, name: &str, data_stream_id: &str| Subscription {
+        subscription_id: id.to_string(),
+        spec: SubscriptionSpec {
+            name: name.to_string(),
+            data_stream_id: data_stream_id.to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut okay_bears = subscription("1", "okay bears sales", "nft-secondary-sales");
+    okay_bears.spec.filters = SubscriptionFilter::default().with(
+        "helloMoonCollectionId",
+        FilterCondition::equal("040de757c0d2b75dcee999ddd47689c4"),
+    );
+    let existing = vec![
+        okay_bears,
+        subscription("2", "jupiter swaps", "dex-trades"),
+        subscription("3", "jupiter swaps", "dex-trades"),
+        subscription("4", "sol transfers", "nft-listings"),
+        subscription("5", "old", "dex-trades"),
+    ];
+
+    let plan = ReconcilePlan::new(&desired, &existing, &ManagedSubscriptions::All).unwrap();
+
+    assert_eq!(
+        plan.actions,
+        vec![
+            ReconcileAction::Delete(existing[2].clone()),
+            ReconcileAction::Delete(existing[4].clone()),
+            ReconcileAction::Update {
+                subscription_id: "1".to_string(),
+                current: existing[0].spec.clone(),
+                desired: desired[0].clone(),
+            },
+            ReconcileAction::Replace {
+                current: existing[3].clone(),
+                desired: desired[2].clone(),
+            },
+        ]
+    );
+    let diff = plan.to_string();
+    assert!(diff.contains(
+        r#"    delivery: {"type":"websocket"} -> {"type":"webhook","url":"https://example.com/hook"}"#
+    ));
+    assert!(diff.contains("    dataStreamId: nft-listings -> token-transfers"));
+    assert!(diff.ends_with("Plan: 0 to create, 1 to update, 1 to replace, 2 to delete."));
+
+    let existing: Vec<Subscription> = desired
+        .iter()
+        .map(|spec| Subscription {
+            spec: spec.clone(),
+            ..Default::default()
+        })
+        .collect();
+    assert!(
+        ReconcilePlan::new(&desired, &existing, &ManagedSubscriptions::All)
+            .unwrap()
+            .is_empty()
+    );
+
+    let duplicated = vec![desired[1].clone(), desired[1].clone()];
+    assert!(ReconcilePlan::new(&duplicated, &[], &ManagedSubscriptions::All).is_err());
+    assert_eq!(desired[1].delivery, DeliveryTarget::Websocket);
+}
+
+#[test]
+fn test_reconcile_plan_name_prefix() {
+    let spec = |name: &str| SubscriptionSpec {
+        name: name.to_string(),
+        data_stream_id: "dex-trades".to_string(),
+        ..Default::default()
+    };
+    let subscription = |id: &str, name: &str| Subscription {
+        subscription_id: id.to_string(),
+        spec: spec(name),
+        ..Default::default()
+    };
+    let existing = vec![
+        subscription("1", "prod/swaps"),
+        subscription("2", "prod/old"),
+        subscription("3", "someone else's stream"),
+    ];
+    let managed = ManagedSubscriptions::NamePrefix("prod/".to_string());
+
+    let plan = ReconcilePlan::new(&[spec("prod/swaps")], &existing, &managed).unwrap();
+    assert_eq!(
+        plan.actions,
+        vec![ReconcileAction::Delete(existing[1].clone())]
+    );
+
+    assert!(ReconcilePlan::new(&[spec("swaps")], &existing, &managed).is_err());
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_reconcile_subscriptions_dry_run() {
+    let api_key = dotenv::var("api_keys").unwrap();
+    let desired = vec![SubscriptionSpec {
+        name: "test/okay bears sales".to_string(),
+        data_stream_id: "nft-secondary-sales".to_string(),
+        ..Default::default()
+    }];
+
+    let managed = ManagedSubscriptions::NamePrefix("test/".to_string());
+    let plan = reconcile_subscriptions(&api_key, &desired, &managed, true)
+        .await
+        .unwrap();
+    println!("{:#?}", plan);
+}
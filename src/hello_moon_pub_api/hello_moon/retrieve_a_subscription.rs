@@ -0,0 +1,35 @@
+//! # Retrieve a subscription
+//!
+//! GET `https://rest-api.hellomoon.io/v1/subscriptions/{subscriptionId}`
+//!
+//! Retrieve the filters and delivery target of a subscription.
+use super::subscription::{subscription_api_url, Subscription};
+use crate::core_call_with_method;
+
+pub async fn retrieve_subscription(
+    api_key: &str,
+    subscription_id: &str,
+) -> anyhow::Result<Subscription> {
+    core_call_with_method::<(), Subscription>(
+        reqwest::Method::GET,
+        None,
+        subscription_api_url(subscription_id),
+        api_key,
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_retrieve_subscription() {
+    let api_key = dotenv::var("api_keys").unwrap();
+    let subscription_id = dotenv::var("subscription_id").unwrap();
+
+    let left = retrieve_subscription(&api_key, &subscription_id)
+        .await
+        .unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: Subscription = serde_json::from_str(&r).unwrap();
+    assert_eq!(left, right);
+}
@@ -1,45 +1,44 @@
-use serde::{Deserialize, Serialize};
-
-use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
-
-#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
-    #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
-    #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
-    #[serde(rename = "paginationToken")]
-    #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
-    /// array of objects
-    data: Option<Vec<IResponse>>,
-    #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+//! # Retrieve the list of streams in your account
+//!
+//! GET `https://rest-api.hellomoon.io/v1/subscriptions`
+//!
+//! List the subscriptions of the account the api key belongs to, one page at a time. The
+//! `paginationToken` of a response is passed back to get the next page.
+use super::subscription::{SubscriptionListResponse, SUBSCRIPTION_API_URL};
+use crate::core_call_with_method;
+
+/// `pagination_token` is empty for the first page.
+pub async fn list_subscriptions(
+    api_key: &str,
+    pagination_token: &str,
+) -> anyhow::Result<SubscriptionListResponse> {
+    let api_url = if pagination_token.is_empty() {
+        SUBSCRIPTION_API_URL.to_string()
+    } else {
+        reqwest::Url::parse_with_params(
+            SUBSCRIPTION_API_URL,
+            [("paginationToken", pagination_token)],
+        )?
+        .to_string()
+    };
+    core_call_with_method::<(), SubscriptionListResponse>(
+        reqwest::Method::GET,
+        None,
+        api_url,
+        api_key,
+    )
+    .await
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
-
+#[ignore = "reason"]
+async fn test_list_subscriptions() {
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = list_subscriptions(&api_key, "").await.unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: SubscriptionListResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
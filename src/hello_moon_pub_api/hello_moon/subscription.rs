@@ -0,0 +1,174 @@
+//! # Subscription
+//!
+//! A subscription delivers the rows of a Hello Moon datastream matching its filters,
+//! either to a webhook or over a websocket.
+//!
+//! Shared model of the subscription endpoints:
+//!
+//! - POST `https://rest-api.hellomoon.io/v1/subscriptions` create a new subscription
+//! - GET `https://rest-api.hellomoon.io/v1/subscriptions` retrieve the list of streams in your account
+//! - GET `https://rest-api.hellomoon.io/v1/subscriptions/{subscriptionId}` retrieve a subscription
+//! - PUT `https://rest-api.hellomoon.io/v1/subscriptions/{subscriptionId}` update a subscription
+//! - DELETE `https://rest-api.hellomoon.io/v1/subscriptions/{subscriptionId}` delete a subscription
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+pub(crate) const SUBSCRIPTION_API_URL: &str = "https://rest-api.hellomoon.io/v1/subscriptions";
+
+pub(crate) fn subscription_api_url(subscription_id: &str) -> String {
+    format!("{}/{}", SUBSCRIPTION_API_URL, subscription_id)
+}
+
+/// The desired state of a subscription, used to create and update it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SubscriptionSpec {
+    /// Name of the subscription, shown in the Hello Moon dashboard
+    pub name: String,
+    /// Id of the datastream the subscription listens to
+    #[serde(rename = "dataStreamId")]
+    pub data_stream_id: String,
+    /// Only the rows matching every filter are delivered
+    #[serde(default)]
+    #[serde(skip_serializing_if = "SubscriptionFilter::is_empty")]
+    pub filters: SubscriptionFilter,
+    /// Where the rows are delivered
+    pub delivery: DeliveryTarget,
+}
+
+/// A subscription as returned by the api.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Subscription {
+    /// Id of the subscription, used to consume, update and delete it
+    #[serde(rename = "subscriptionId")]
+    pub subscription_id: String,
+    #[serde(flatten)]
+    pub spec: SubscriptionSpec,
+    /// ISO 8601 time the subscription was created at
+    #[serde(rename = "createdAt")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    /// Whether the subscription is currently delivering rows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+}
+
+/// Conditions on the columns of the datastream rows, keyed by column name — e.g. `nftMint`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(transparent)]
+pub struct SubscriptionFilter(pub BTreeMap<String, FilterCondition>);
+
+impl SubscriptionFilter {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add a condition on `column`, replacing the previous one.
+    pub fn with(mut self, column: impl Into<String>, condition: FilterCondition) -> Self {
+        self.0.insert(column.into(), condition);
+        self
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct FilterCondition {
+    pub operator: FilterOperator,
+    /// A string or a number, or an array of them for the `in` operator
+    pub value: serde_json::Value,
+}
+
+impl FilterCondition {
+    pub fn new(operator: FilterOperator, value: impl Into<serde_json::Value>) -> Self {
+        Self {
+            operator,
+            value: value.into(),
+        }
+    }
+
+    pub fn equal(value: impl Into<serde_json::Value>) -> Self {
+        Self::new(FilterOperator::Equal, value)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub enum FilterOperator {
+    #[serde(rename = "=")]
+    Equal,
+    #[serde(rename = "!=")]
+    NotEqual,
+    #[serde(rename = ">")]
+    Greater,
+    #[serde(rename = ">=")]
+    GreaterOrEqual,
+    #[serde(rename = "<")]
+    Less,
+    #[serde(rename = "<=")]
+    LessOrEqual,
+    #[serde(rename = "in")]
+    In,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(tag = "type")]
+pub enum DeliveryTarget {
+    /// The rows are posted to `url` as they arrive
+    #[serde(rename = "webhook")]
+    Webhook { url: String },
+    /// The rows are consumed over a websocket with the subscription id
+    #[default]
+    #[serde(rename = "websocket")]
+    Websocket,
+}
+
+/// Response of the list endpoint
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SubscriptionListResponse {
+    /// array of objects
+    pub data: Option<Vec<Subscription>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[test]
+fn test_subscription_serde() {
+    let spec = SubscriptionSpec {
+        name: "okay bears sales".to_string(),
+        data_stream_id: "nft-secondary-sales".to_string(),
+        filters: SubscriptionFilter::default().with(
+            "helloMoonCollectionId",
+            FilterCondition::equal("040de757c0d2b75dcee999ddd47689c4"),
+        ),
+        delivery: DeliveryTarget::Webhook {
+            url: "https://example.com/hook".to_string(),
+        },
+    };
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "name": "okay bears sales",
+            "dataStreamId": "nft-secondary-sales",
+            "filters": {
+                "helloMoonCollectionId": {
+                    "operator": "=",
+                    "value": "040de757c0d2b75dcee999ddd47689c4"
+                }
+            },
+            "delivery": { "type": "webhook", "url": "https://example.com/hook" }
+        })
+    );
+
+    let subscription: Subscription = serde_json::from_value(serde_json::json!({
+        "subscriptionId": "sub",
+        "name": "okay bears sales",
+        "dataStreamId": "nft-secondary-sales",
+        "delivery": { "type": "websocket" },
+        "active": true
+    }))
+    .unwrap();
+    assert_eq!(subscription.subscription_id, "sub");
+    assert_eq!(subscription.spec.delivery, DeliveryTarget::Websocket);
+    assert!(subscription.spec.filters.is_empty());
+}
@@ -1 +1,6 @@
+pub mod events;
 pub mod hello_moon;
+pub mod streams;
+#[cfg(feature = "webhook")]
+pub mod webhook;
+pub mod websocket;
@@ -0,0 +1,4 @@
+pub mod parsed_account_update;
+pub mod stream;
+pub mod unparsed_account_update;
+pub mod unparsed_transaction;
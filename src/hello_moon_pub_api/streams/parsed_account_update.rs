@@ -0,0 +1,67 @@
+//! # ParsedAccountUpdate stream
+//!
+//! POST `https://rest-api.hellomoon.io/v1/streams/parsed-account-update`
+//!
+//! PUT `https://rest-api.hellomoon.io/v1/streams/parsed-account-update/{streamId}`
+//!
+//! Streams the updates of accounts owned by a program Hello Moon parses,
+//! with the account data decoded into named fields.
+use serde::{Deserialize, Serialize};
+
+use super::stream::{create_stream, update_stream, Commitment, Stream};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct ParsedAccountUpdateSpec {
+    /// Name of the stream
+    pub name: String,
+    /// The program owning the accounts, it must be one of the programs Hello Moon parses
+    #[serde(rename = "programId")]
+    pub program_id: String,
+    /// Only the accounts of these parsed types are streamed, all of them when empty — e.g. `TokenAccount`
+    #[serde(rename = "accountTypes")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub account_types: Vec<String>,
+    /// Only these accounts are streamed, all the accounts of the program when empty
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub accounts: Vec<String>,
+    #[serde(default)]
+    pub commitment: Commitment,
+}
+
+impl ParsedAccountUpdateSpec {
+    pub const KIND: &'static str = "parsed-account-update";
+}
+
+pub async fn create_parsed_account_update_stream(
+    api_key: &str,
+    request: &ParsedAccountUpdateSpec,
+) -> anyhow::Result<Stream<ParsedAccountUpdateSpec>> {
+    create_stream(api_key, ParsedAccountUpdateSpec::KIND, request).await
+}
+
+pub async fn update_parsed_account_update_stream(
+    api_key: &str,
+    stream_id: &str,
+    request: &ParsedAccountUpdateSpec,
+) -> anyhow::Result<Stream<ParsedAccountUpdateSpec>> {
+    update_stream(api_key, ParsedAccountUpdateSpec::KIND, stream_id, request).await
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_create_parsed_account_update_stream() {
+    let request = ParsedAccountUpdateSpec {
+        name: "hellomoon-rs test".to_string(),
+        program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+        account_types: vec!["TokenAccount".to_string()],
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+    let stream = create_parsed_account_update_stream(&api_key, &request)
+        .await
+        .unwrap();
+    assert_eq!(stream.spec, request);
+}
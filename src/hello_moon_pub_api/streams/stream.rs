@@ -0,0 +1,168 @@
+//! # Stream
+//!
+//! A stream is a datastream defined by the user, which subscriptions can then consume.
+//!
+//! Shared model of the stream endpoints:
+//!
+//! - POST `https://rest-api.hellomoon.io/v1/streams/{kind}` create a stream
+//! - PUT `https://rest-api.hellomoon.io/v1/streams/{kind}/{streamId}` update a stream
+//!
+//! where `kind` is `parsed-account-update`, `unparsed-account-update` or `unparsed-transaction`.
+//!
+//! Every spec is plain serde, so streams can be kept as versioned json or toml config:
+//!
+//! ```toml
+//! kind = "UnparsedTransaction"
+//! name = "jupiter swaps"
+//! accountInclude = ["JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB"]
+//! commitment = "finalized"
+//! ```
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::parsed_account_update::ParsedAccountUpdateSpec;
+use super::unparsed_account_update::UnparsedAccountUpdateSpec;
+use super::unparsed_transaction::UnparsedTransactionSpec;
+use crate::core_call_with_method;
+
+const STREAM_API_URL: &str = "https://rest-api.hellomoon.io/v1/streams";
+
+/// The bank state the updates are read from.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Commitment {
+    /// The most recent block, which can still be skipped
+    #[serde(rename = "processed")]
+    Processed,
+    /// A block voted on by a supermajority of the cluster
+    #[default]
+    #[serde(rename = "confirmed")]
+    Confirmed,
+    /// A block rooted by a supermajority of the cluster
+    #[serde(rename = "finalized")]
+    Finalized,
+}
+
+/// A stream as returned by the api.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Stream<Spec> {
+    /// Id of the stream, used as `dataStreamId` of the subscriptions consuming it
+    #[serde(rename = "streamId")]
+    pub stream_id: String,
+    #[serde(flatten)]
+    pub spec: Spec,
+}
+
+/// A stream spec of any kind, tagged by `kind`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(tag = "kind")]
+pub enum StreamSpec {
+    ParsedAccountUpdate(ParsedAccountUpdateSpec),
+    UnparsedAccountUpdate(UnparsedAccountUpdateSpec),
+    UnparsedTransaction(UnparsedTransactionSpec),
+}
+
+impl StreamSpec {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::ParsedAccountUpdate(spec) => &spec.name,
+            Self::UnparsedAccountUpdate(spec) => &spec.name,
+            Self::UnparsedTransaction(spec) => &spec.name,
+        }
+    }
+
+    /// Create the stream, returning its id.
+    pub async fn create(&self, api_key: &str) -> anyhow::Result<String> {
+        Ok(match self {
+            Self::ParsedAccountUpdate(spec) => {
+                create_stream(api_key, ParsedAccountUpdateSpec::KIND, spec)
+                    .await?
+                    .stream_id
+            }
+            Self::UnparsedAccountUpdate(spec) => {
+                create_stream(api_key, UnparsedAccountUpdateSpec::KIND, spec)
+                    .await?
+                    .stream_id
+            }
+            Self::UnparsedTransaction(spec) => {
+                create_stream(api_key, UnparsedTransactionSpec::KIND, spec)
+                    .await?
+                    .stream_id
+            }
+        })
+    }
+
+    /// Replace the definition of the stream `stream_id` with this spec.
+    pub async fn update(&self, api_key: &str, stream_id: &str) -> anyhow::Result<()> {
+        match self {
+            Self::ParsedAccountUpdate(spec) => {
+                update_stream(api_key, ParsedAccountUpdateSpec::KIND, stream_id, spec).await?;
+            }
+            Self::UnparsedAccountUpdate(spec) => {
+                update_stream(api_key, UnparsedAccountUpdateSpec::KIND, stream_id, spec).await?;
+            }
+            Self::UnparsedTransaction(spec) => {
+                update_stream(api_key, UnparsedTransactionSpec::KIND, stream_id, spec).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) async fn create_stream<Spec: Serialize + DeserializeOwned + Clone>(
+    api_key: &str,
+    kind: &str,
+    spec: &Spec,
+) -> anyhow::Result<Stream<Spec>> {
+    core_call_with_method::<Spec, Stream<Spec>>(
+        reqwest::Method::POST,
+        Some(spec.clone()),
+        format!("{}/{}", STREAM_API_URL, kind),
+        api_key,
+    )
+    .await
+}
+
+pub(crate) async fn update_stream<Spec: Serialize + DeserializeOwned + Clone>(
+    api_key: &str,
+    kind: &str,
+    stream_id: &str,
+    spec: &Spec,
+) -> anyhow::Result<Stream<Spec>> {
+    core_call_with_method::<Spec, Stream<Spec>>(
+        reqwest::Method::PUT,
+        Some(spec.clone()),
+        format!("{}/{}/{}", STREAM_API_URL, kind, stream_id),
+        api_key,
+    )
+    .await
+}
+
+#[test]
+fn test_stream_spec_from_toml() {
+    let spec: StreamSpec = toml::from_str(
+        r#"
+        kind = "UnparsedTransaction"
+        name = "jupiter swaps"
+        accountInclude = ["JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB"]
+        commitment = "finalized"
+        "#,
+    )
+    .unwrap();
+
+    let StreamSpec::UnparsedTransaction(transaction) = &spec else {
+        panic!("unexpected kind: {:?}", spec);
+    };
+    assert_eq!(spec.name(), "jupiter swaps");
+    assert_eq!(transaction.commitment, Commitment::Finalized);
+    assert!(transaction.account_exclude.is_empty());
+    assert!(!transaction.include_failed);
+
+    assert_eq!(
+        serde_json::to_value(&spec).unwrap(),
+        serde_json::json!({
+            "kind": "UnparsedTransaction",
+            "name": "jupiter swaps",
+            "accountInclude": ["JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB"],
+            "commitment": "finalized"
+        })
+    );
+}
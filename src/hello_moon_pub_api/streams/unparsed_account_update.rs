@@ -0,0 +1,66 @@
+//! # UnparsedAccountUpdate stream
+//!
+//! POST `https://rest-api.hellomoon.io/v1/streams/unparsed-account-update`
+//!
+//! PUT `https://rest-api.hellomoon.io/v1/streams/unparsed-account-update/{streamId}`
+//!
+//! Streams the raw updates of any account, with the account data base64 encoded.
+use serde::{Deserialize, Serialize};
+
+use super::stream::{create_stream, update_stream, Commitment, Stream};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct UnparsedAccountUpdateSpec {
+    /// Name of the stream
+    pub name: String,
+    /// Only the accounts owned by one of these programs are streamed
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+    /// Only these accounts are streamed
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub accounts: Vec<String>,
+    /// Only the accounts with exactly this data length in bytes are streamed
+    #[serde(rename = "dataSize")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_size: Option<u64>,
+    #[serde(default)]
+    pub commitment: Commitment,
+}
+
+impl UnparsedAccountUpdateSpec {
+    pub const KIND: &'static str = "unparsed-account-update";
+}
+
+pub async fn create_unparsed_account_update_stream(
+    api_key: &str,
+    request: &UnparsedAccountUpdateSpec,
+) -> anyhow::Result<Stream<UnparsedAccountUpdateSpec>> {
+    create_stream(api_key, UnparsedAccountUpdateSpec::KIND, request).await
+}
+
+pub async fn update_unparsed_account_update_stream(
+    api_key: &str,
+    stream_id: &str,
+    request: &UnparsedAccountUpdateSpec,
+) -> anyhow::Result<Stream<UnparsedAccountUpdateSpec>> {
+    update_stream(api_key, UnparsedAccountUpdateSpec::KIND, stream_id, request).await
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_create_unparsed_account_update_stream() {
+    let request = UnparsedAccountUpdateSpec {
+        name: "hellomoon-rs test".to_string(),
+        owners: vec!["TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string()],
+        data_size: Some(165),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+    let stream = create_unparsed_account_update_stream(&api_key, &request)
+        .await
+        .unwrap();
+    assert_eq!(stream.spec, request);
+}
@@ -0,0 +1,74 @@
+//! # UnparsedTransaction stream
+//!
+//! POST `https://rest-api.hellomoon.io/v1/streams/unparsed-transaction`
+//!
+//! PUT `https://rest-api.hellomoon.io/v1/streams/unparsed-transaction/{streamId}`
+//!
+//! Streams the raw transactions touching a set of accounts.
+use serde::{Deserialize, Serialize};
+
+use super::stream::{create_stream, update_stream, Commitment, Stream};
+use crate::is_false;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct UnparsedTransactionSpec {
+    /// Name of the stream
+    pub name: String,
+    /// The transactions using any of these accounts are streamed
+    #[serde(rename = "accountInclude")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub account_include: Vec<String>,
+    /// The transactions using any of these accounts are not streamed
+    #[serde(rename = "accountExclude")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub account_exclude: Vec<String>,
+    /// Only the transactions using all of these accounts are streamed
+    #[serde(rename = "accountRequired")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub account_required: Vec<String>,
+    /// Whether failed transactions are streamed too
+    #[serde(rename = "includeFailed")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub include_failed: bool,
+    #[serde(default)]
+    pub commitment: Commitment,
+}
+
+impl UnparsedTransactionSpec {
+    pub const KIND: &'static str = "unparsed-transaction";
+}
+
+pub async fn create_unparsed_transaction_stream(
+    api_key: &str,
+    request: &UnparsedTransactionSpec,
+) -> anyhow::Result<Stream<UnparsedTransactionSpec>> {
+    create_stream(api_key, UnparsedTransactionSpec::KIND, request).await
+}
+
+pub async fn update_unparsed_transaction_stream(
+    api_key: &str,
+    stream_id: &str,
+    request: &UnparsedTransactionSpec,
+) -> anyhow::Result<Stream<UnparsedTransactionSpec>> {
+    update_stream(api_key, UnparsedTransactionSpec::KIND, stream_id, request).await
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_create_unparsed_transaction_stream() {
+    let request = UnparsedTransactionSpec {
+        name: "hellomoon-rs test".to_string(),
+        account_include: vec!["JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB".to_string()],
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+    let stream = create_unparsed_transaction_stream(&api_key, &request)
+        .await
+        .unwrap();
+    assert_eq!(stream.spec, request);
+}
@@ -0,0 +1,342 @@
+//! # Datastream webhook
+//!
+//! Receive the [`StreamMessage`]s of subscriptions delivering to a webhook. Requires the
+//! `webhook` feature.
+//!
+//! [`WebhookReceiver`] is an axum service accepting the deliveries with a `POST` on `/`. Each
+//! message is handed to a [`WebhookHandler`], which is either an async closure or a
+//! `tokio::sync::mpsc::Sender<StreamMessage>`. A delivery is answered with:
+//!
+//! - `200` when every message was handled
+//! - `400` when the body is not a stream message nor a batch of them
+//! - `500` when the handler failed, so Hello Moon delivers the messages again
+//! - `503` when a message is still being handled for an earlier delivery, so it is delivered
+//!   again once that attempt succeeded or failed
+//!
+//! Messages are deduplicated on their subscription id and sequence number, or on their content
+//! when they have no sequence number, so a redelivery only reaches the handler with the
+//! messages that were not handled yet. A message counts as handled once the handler succeeded.
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use tokio::sync::mpsc;
+
+use super::events::StreamMessage;
+
+/// Handles the messages received by a [`WebhookReceiver`].
+pub trait WebhookHandler: Send + Sync + 'static {
+    fn handle(&self, message: StreamMessage) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+impl<F, Fut> WebhookHandler for F
+where
+    F: Fn(StreamMessage) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = anyhow::Result<()>> + Send,
+{
+    fn handle(&self, message: StreamMessage) -> impl Future<Output = anyhow::Result<()>> + Send {
+        self(message)
+    }
+}
+
+impl WebhookHandler for mpsc::Sender<StreamMessage> {
+    async fn handle(&self, message: StreamMessage) -> anyhow::Result<()> {
+        Ok(self.send(message).await?)
+    }
+}
+
+/// Remembers the keys of the last `capacity` handled messages and of the messages being handled.
+#[derive(Debug)]
+struct Deduplicator {
+    capacity: usize,
+    seen: HashSet<String>,
+    order: VecDeque<String>,
+    in_flight: HashSet<String>,
+}
+
+/// What to do with a received message.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Admission {
+    /// Not handled yet, the caller now owns the key until `finish`
+    Handle,
+    /// Already handled, drop it
+    Handled,
+    /// Being handled for an earlier delivery
+    InFlight,
+}
+
+impl Deduplicator {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+            in_flight: HashSet::new(),
+        }
+    }
+
+    fn key(message: &StreamMessage) -> String {
+        match message.sequence {
+            Some(sequence) => format!("{}:{}", message.subscription_id, sequence),
+            None => serde_json::to_string(message).unwrap_or_default(),
+        }
+    }
+
+    fn admit(&mut self, key: &str) -> Admission {
+        if self.capacity == 0 {
+            Admission::Handle
+        } else if self.seen.contains(key) {
+            Admission::Handled
+        } else if !self.in_flight.insert(key.to_string()) {
+            Admission::InFlight
+        } else {
+            Admission::Handle
+        }
+    }
+
+    /// Release a key returned by `admit`, and remember it when the message was handled.
+    fn finish(&mut self, key: &str, handled: bool) {
+        if !self.in_flight.remove(key) || !handled {
+            return;
+        }
+        self.seen.insert(key.to_string());
+        self.order.push_back(key.to_string());
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Releases an admitted key when dropped, also when the request is cancelled mid-handling.
+struct InFlight<'a> {
+    deduplicator: &'a Mutex<Deduplicator>,
+    key: &'a str,
+    handled: bool,
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.deduplicator
+            .lock()
+            .unwrap()
+            .finish(self.key, self.handled);
+    }
+}
+
+struct Receiver<H> {
+    handler: H,
+    deduplicator: Mutex<Deduplicator>,
+}
+
+/// An http receiver for webhook deliveries.
+pub struct WebhookReceiver<H> {
+    handler: H,
+    dedup_capacity: usize,
+}
+
+impl<H: WebhookHandler> WebhookReceiver<H> {
+    pub fn new(handler: H) -> Self {
+        Self {
+            handler,
+            dedup_capacity: 10_000,
+        }
+    }
+
+    /// Number of handled messages remembered to drop redeliveries, 0 disables deduplication.
+    pub fn dedup_capacity(mut self, capacity: usize) -> Self {
+        self.dedup_capacity = capacity;
+        self
+    }
+
+    /// The axum router, to be served on its own or nested in an existing application.
+    pub fn router(self) -> Router {
+        let receiver = Arc::new(Receiver {
+            handler: self.handler,
+            deduplicator: Mutex::new(Deduplicator::new(self.dedup_capacity)),
+        });
+        Router::new()
+            .route("/", post(receive::<H>))
+            .with_state(receiver)
+    }
+
+    /// Listen on `address` until the server fails.
+    pub async fn serve(self, address: SocketAddr) -> anyhow::Result<()> {
+        let listener = tokio::net::TcpListener::bind(address).await?;
+        axum::serve(listener, self.router()).await?;
+        Ok(())
+    }
+}
+
+async fn receive<H: WebhookHandler>(
+    State(receiver): State<Arc<Receiver<H>>>,
+    body: String,
+) -> StatusCode {
+    let messages = match StreamMessage::parse_batch(&body) {
+        Ok(messages) => messages,
+        Err(e) => {
+            log::warn!("invalid webhook delivery: {}", e);
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+
+    for message in messages {
+        let key = Deduplicator::key(&message);
+        let admission = receiver.deduplicator.lock().unwrap().admit(&key);
+        match admission {
+            Admission::Handle => {}
+            Admission::Handled => {
+                log::debug!("dropping redelivered message {}", key);
+                continue;
+            }
+            Admission::InFlight => {
+                log::debug!("message {} is being handled by an earlier delivery", key);
+                return StatusCode::SERVICE_UNAVAILABLE;
+            }
+        }
+        let mut in_flight = InFlight {
+            deduplicator: &receiver.deduplicator,
+            key: &key,
+            handled: false,
+        };
+        if let Err(e) = receiver.handler.handle(message).await {
+            log::warn!("webhook handler failed on message {}: {}", key, e);
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+        in_flight.handled = true;
+    }
+    StatusCode::OK
+}
+
+#[tokio::test]
+async fn test_webhook_receiver() {
+    let (sender, mut received) = mpsc::channel(16);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let router = WebhookReceiver::new(sender).router();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let message = |sequence: u64| {
+        format!(
+            r#"{{"subscriptionId":"sub","sequence":{},"type":"UnparsedAccountUpdate","data":{{"account":"a","owner":"o","lamports":1,"data":"AA==","slot":{}}}}}"#,
+            sequence, sequence
+        )
+    };
+    let client = reqwest::Client::new();
+    let post = |body: String| {
+        client
+            .post(format!("http://{}/", address))
+            .body(body)
+            .send()
+    };
+
+    let status = post(message(1)).await.unwrap().status();
+    assert_eq!(status, reqwest::StatusCode::OK);
+    // a redelivered batch only hands over the new message
+    let status = post(format!("[{},{}]", message(1), message(2)))
+        .await
+        .unwrap()
+        .status();
+    assert_eq!(status, reqwest::StatusCode::OK);
+    let status = post("not json".to_string()).await.unwrap().status();
+    assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+
+    assert_eq!(received.recv().await.unwrap().sequence, Some(1));
+    assert_eq!(received.recv().await.unwrap().sequence, Some(2));
+    assert!(received.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_webhook_receiver_redelivers_failed_messages() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let handler = {
+        let calls = calls.clone();
+        move |_message: StreamMessage| {
+            let calls = calls.clone();
+            async move {
+                if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    anyhow::bail!("not ready");
+                }
+                Ok(())
+            }
+        }
+    };
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let router = WebhookReceiver::new(handler).router();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let body = r#"{"subscriptionId":"sub","type":"SomethingNew","data":{}}"#;
+    let client = reqwest::Client::new();
+    let post = || {
+        client
+            .post(format!("http://{}/", address))
+            .body(body)
+            .send()
+    };
+
+    let status = post().await.unwrap().status();
+    assert_eq!(status, reqwest::StatusCode::INTERNAL_SERVER_ERROR);
+    let status = post().await.unwrap().status();
+    assert_eq!(status, reqwest::StatusCode::OK);
+    let status = post().await.unwrap().status();
+    assert_eq!(status, reqwest::StatusCode::OK);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_webhook_receiver_concurrent_redelivery() {
+    use tokio::sync::{oneshot, Notify};
+
+    let started = Arc::new(Notify::new());
+    let (fail, failed) = oneshot::channel::<()>();
+    let failed = Arc::new(tokio::sync::Mutex::new(Some(failed)));
+    let handler = {
+        let started = started.clone();
+        move |_message: StreamMessage| {
+            let started = started.clone();
+            let failed = failed.clone();
+            async move {
+                // the first attempt blocks until told to fail, the next ones succeed
+                let Some(failed) = failed.lock().await.take() else {
+                    return Ok(());
+                };
+                started.notify_one();
+                let _ = failed.await;
+                anyhow::bail!("not ready")
+            }
+        }
+    };
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let router = WebhookReceiver::new(handler).router();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let body = r#"{"subscriptionId":"sub","sequence":7,"type":"SomethingNew","data":{}}"#;
+    let client = reqwest::Client::new();
+    let post = || {
+        client
+            .post(format!("http://{}/", address))
+            .body(body)
+            .send()
+    };
+
+    let first = tokio::spawn(post());
+    started.notified().await;
+    let status = post().await.unwrap().status();
+    assert_eq!(status, reqwest::StatusCode::SERVICE_UNAVAILABLE);
+
+    fail.send(()).unwrap();
+    let status = first.await.unwrap().unwrap().status();
+    assert_eq!(status, reqwest::StatusCode::INTERNAL_SERVER_ERROR);
+    let status = post().await.unwrap().status();
+    assert_eq!(status, reqwest::StatusCode::OK);
+}
@@ -0,0 +1,356 @@
+//! # Datastream websocket
+//!
+//! `wss://kiki-stream.hellomoon.io`
+//!
+//! Consume a subscription delivering over a websocket. After connecting, the consumer
+//! authenticates with
+//!
+//! ```json
+//! { "action": "subscribe", "apiKey": "...", "subscriptionId": "..." }
+//! ```
+//!
+//! and the server starts pushing the [`StreamMessage`]s of the subscription.
+//!
+//! [`DatastreamConsumer`] is an async `Stream` of [`DatastreamItem`]s. When the connection drops
+//! it reconnects with an exponential backoff, and reports a [`DatastreamItem::Gap`] whenever the
+//! sequence numbers of the received messages skip some. A row that fails to decode is reported as
+//! an `Err` item and the other rows of its batch are still delivered, its sequence number still
+//! counts as received.
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+
+use super::events::StreamMessage;
+
+pub const DATASTREAM_WEBSOCKET_URL: &str = "wss://kiki-stream.hellomoon.io";
+
+#[derive(Debug, Clone)]
+pub struct DatastreamConfig {
+    pub url: String,
+    pub api_key: String,
+    pub subscription_id: String,
+    /// Delay before the first reconnection, doubled after every failed attempt
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Give up after this many reconnections in a row without receiving a message, never when `None`
+    pub max_reconnects: Option<usize>,
+}
+
+impl DatastreamConfig {
+    pub fn new(api_key: impl Into<String>, subscription_id: impl Into<String>) -> Self {
+        Self {
+            url: DATASTREAM_WEBSOCKET_URL.to_string(),
+            api_key: api_key.into(),
+            subscription_id: subscription_id.into(),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_reconnects: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DatastreamItem {
+    Message(Box<StreamMessage>),
+    /// The messages with a sequence number in `expected..received` were not received
+    Gap {
+        expected: u64,
+        received: u64,
+    },
+    /// The connection dropped and was established again after `attempt` tries,
+    /// messages without a sequence number may have been missed meanwhile
+    Reconnected {
+        attempt: usize,
+    },
+}
+
+#[derive(Serialize)]
+struct SubscribeRequest<'a> {
+    action: &'a str,
+    #[serde(rename = "apiKey")]
+    api_key: &'a str,
+    #[serde(rename = "subscriptionId")]
+    subscription_id: &'a str,
+}
+
+/// A running websocket consumer, the connection is closed when it is dropped.
+pub struct DatastreamConsumer {
+    receiver: mpsc::Receiver<anyhow::Result<DatastreamItem>>,
+    task: JoinHandle<()>,
+}
+
+impl DatastreamConsumer {
+    /// Start consuming the subscription in the background.
+    pub fn subscribe(config: DatastreamConfig) -> Self {
+        let (sender, receiver) = mpsc::channel(1024);
+        let task = tokio::spawn(run(config, sender));
+        Self { receiver, task }
+    }
+}
+
+impl Stream for DatastreamConsumer {
+    type Item = anyhow::Result<DatastreamItem>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for DatastreamConsumer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn run(config: DatastreamConfig, sender: mpsc::Sender<anyhow::Result<DatastreamItem>>) {
+    let mut attempt = 0;
+    let mut backoff = config.initial_backoff;
+    let mut last_sequence: Option<u64> = None;
+
+    loop {
+        match consume(&config, attempt, &mut last_sequence, &sender).await {
+            Ok(true) => {
+                attempt = 0;
+                backoff = config.initial_backoff;
+            }
+            Ok(false) => {}
+            Err(e) => log::warn!("datastream {}: {}", config.subscription_id, e),
+        }
+        if sender.is_closed() {
+            return;
+        }
+
+        attempt += 1;
+        if config.max_reconnects.is_some_and(|max| attempt > max) {
+            let _ = sender
+                .send(Err(anyhow::anyhow!(
+                    "datastream {}: gave up after {} reconnections",
+                    config.subscription_id,
+                    attempt - 1
+                )))
+                .await;
+            return;
+        }
+        log::info!(
+            "datastream {}: reconnecting in {:?} (attempt {})",
+            config.subscription_id,
+            backoff,
+            attempt
+        );
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(config.max_backoff);
+    }
+}
+
+/// Consume one connection until it drops, returns whether any message was received.
+async fn consume(
+    config: &DatastreamConfig,
+    attempt: usize,
+    last_sequence: &mut Option<u64>,
+    sender: &mpsc::Sender<anyhow::Result<DatastreamItem>>,
+) -> anyhow::Result<bool> {
+    let (mut socket, _) = tokio_tungstenite::connect_async(config.url.as_str()).await?;
+    let subscribe = SubscribeRequest {
+        action: "subscribe",
+        api_key: &config.api_key,
+        subscription_id: &config.subscription_id,
+    };
+    socket
+        .send(Message::Text(serde_json::to_string(&subscribe)?))
+        .await?;
+    if attempt > 0 {
+        sender
+            .send(Ok(DatastreamItem::Reconnected { attempt }))
+            .await?;
+    }
+
+    let mut received = false;
+    while let Some(frame) = socket.next().await {
+        let text = match frame? {
+            Message::Text(text) => text,
+            Message::Binary(bytes) => String::from_utf8(bytes)?,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let Some(rows) = StreamMessage::parse_rows(&text) else {
+            // acknowledgements such as "You have successfully subscribed"
+            log::debug!("datastream {}: {}", config.subscription_id, text);
+            continue;
+        };
+        for row in rows {
+            let message = match row {
+                Ok(message) => message,
+                Err(e) => {
+                    log::warn!(
+                        "datastream {}: failed to decode a message: {}",
+                        config.subscription_id,
+                        e
+                    );
+                    if let Some(gap) = track_sequence(last_sequence, e.sequence) {
+                        sender.send(Ok(gap)).await?;
+                    }
+                    sender
+                        .send(Err(anyhow::anyhow!(
+                            "datastream {}: failed to decode a message: {}",
+                            config.subscription_id,
+                            e
+                        )))
+                        .await?;
+                    continue;
+                }
+            };
+            received = true;
+            if let Some(gap) = track_sequence(last_sequence, message.sequence) {
+                sender.send(Ok(gap)).await?;
+            }
+            sender
+                .send(Ok(DatastreamItem::Message(Box::new(message))))
+                .await?;
+        }
+    }
+    Ok(received)
+}
+
+/// Record a received sequence number, returns the gap since the last one if some were skipped.
+fn track_sequence(
+    last_sequence: &mut Option<u64>,
+    sequence: Option<u64>,
+) -> Option<DatastreamItem> {
+    let sequence = sequence?;
+    let gap = last_sequence
+        .filter(|last| sequence.saturating_sub(*last) > 1)
+        .map(|last| DatastreamItem::Gap {
+            expected: last + 1,
+            received: sequence,
+        });
+    *last_sequence = Some(last_sequence.map_or(sequence, |last| last.max(sequence)));
+    gap
+}
+
+#[test]
+fn test_track_sequence() {
+    let mut last_sequence = None;
+    assert_eq!(track_sequence(&mut last_sequence, Some(1)), None);
+    assert_eq!(track_sequence(&mut last_sequence, None), None);
+    assert_eq!(
+        track_sequence(&mut last_sequence, Some(4)),
+        Some(DatastreamItem::Gap {
+            expected: 2,
+            received: 4
+        })
+    );
+    assert_eq!(track_sequence(&mut last_sequence, Some(3)), None);
+    assert_eq!(last_sequence, Some(4));
+
+    let mut last_sequence = Some(u64::MAX);
+    assert_eq!(track_sequence(&mut last_sequence, Some(u64::MAX)), None);
+    assert_eq!(track_sequence(&mut last_sequence, Some(0)), None);
+}
+
+#[tokio::test]
+async fn test_datastream_consumer() {
+    use tokio::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let message = |sequence: u64| {
+        format!(
+            r#"{{"subscriptionId":"sub","sequence":{},"type":"ParsedAccountUpdate","data":{{"account":"a","programId":"p","accountType":null,"slot":{},"data":{{}}}}}}"#,
+            sequence, sequence
+        )
+    };
+    let server = tokio::spawn(async move {
+        // the first connection delivers 1 and 2 then drops, the second one resumes at 5
+        for batch in [vec![1, 2], vec![5]] {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let subscribe = socket.next().await.unwrap().unwrap().into_text().unwrap();
+            assert!(subscribe.contains(r#""subscriptionId":"sub""#));
+            socket
+                .send(Message::Text("You have successfully subscribed".into()))
+                .await
+                .unwrap();
+            for sequence in batch {
+                socket.send(Message::Text(message(sequence))).await.unwrap();
+            }
+            socket.close(None).await.unwrap();
+        }
+    });
+
+    let mut config = DatastreamConfig::new("key", "sub");
+    config.url = format!("ws://{}", address);
+    config.initial_backoff = Duration::from_millis(10);
+    let consumer = DatastreamConsumer::subscribe(config);
+    let items: Vec<DatastreamItem> = consumer.take(5).map(|item| item.unwrap()).collect().await;
+    server.await.unwrap();
+
+    let sequence = |item: &DatastreamItem| match item {
+        DatastreamItem::Message(message) => message.sequence,
+        _ => None,
+    };
+    assert_eq!(sequence(&items[0]), Some(1));
+    assert_eq!(sequence(&items[1]), Some(2));
+    assert_eq!(items[2], DatastreamItem::Reconnected { attempt: 1 });
+    assert_eq!(
+        items[3],
+        DatastreamItem::Gap {
+            expected: 3,
+            received: 5
+        }
+    );
+    assert_eq!(sequence(&items[4]), Some(5));
+}
+
+#[tokio::test]
+async fn test_datastream_consumer_malformed_row() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+        socket.next().await.unwrap().unwrap();
+        let batch = r#"[
+            { "subscriptionId": "sub", "sequence": 1, "type": "SomethingNew", "data": {} },
+            { "subscriptionId": "sub", "sequence": 2, "type": "DexTrade", "data": { "slot": "x" } },
+            { "subscriptionId": "sub", "sequence": 3, "type": "SomethingNew", "data": {} }
+        ]"#;
+        socket.send(Message::Text(batch.into())).await.unwrap();
+        socket.close(None).await.unwrap();
+    });
+
+    let mut config = DatastreamConfig::new("key", "sub");
+    config.url = format!("ws://{}", address);
+    let consumer = DatastreamConsumer::subscribe(config);
+    let items: Vec<anyhow::Result<DatastreamItem>> = consumer.take(3).collect().await;
+    server.await.unwrap();
+
+    let sequence = |item: &anyhow::Result<DatastreamItem>| match item {
+        Ok(DatastreamItem::Message(message)) => message.sequence,
+        _ => None,
+    };
+    assert_eq!(sequence(&items[0]), Some(1));
+    assert!(items[1].is_err());
+    assert_eq!(sequence(&items[2]), Some(3));
+}
+
+#[tokio::test]
+async fn test_datastream_consumer_gives_up() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    drop(listener);
+
+    let mut config = DatastreamConfig::new("key", "sub");
+    config.url = format!("ws://{}", address);
+    config.initial_backoff = Duration::from_millis(1);
+    config.max_reconnects = Some(2);
+    let mut consumer = DatastreamConsumer::subscribe(config);
+
+    assert!(consumer.next().await.unwrap().is_err());
+    assert!(consumer.next().await.is_none());
+}
@@ -2,8 +2,11 @@
 
 use serde::{de::DeserializeOwned, Serialize};
 
+pub mod analysis;
 pub mod dimension_facts;
 pub mod hello_moon_pub_api;
+pub mod portfolio;
+pub mod rpc;
 pub mod summary;
 
 pub const HELLOMOON_ROOT_URL: &str = "https://rest-api.hellomoon.io/v0";
@@ -13,33 +16,74 @@ pub async fn core_call<Request: Serialize, Response: DeserializeOwned>(
     api_url: String,
     api_key: &str,
 ) -> anyhow::Result<Response> {
-    let response = if let Some(request) = request {
+    core_call_with_method(reqwest::Method::POST, request, api_url, api_key).await
+}
+
+/// Same as `core_call` with an explicit http method, for the endpoints that are not `POST`.
+///
+/// An empty response body is deserialized as `null`, so `()` or `Option` can be used as `Response`.
+/// A response with a non-2xx status is an error carrying the status and the body.
+pub async fn core_call_with_method<Request: Serialize, Response: DeserializeOwned>(
+    method: reqwest::Method,
+    request: Option<Request>,
+    api_url: String,
+    api_key: &str,
+) -> anyhow::Result<Response> {
+    let builder = reqwest::Client::new()
+        .request(method.clone(), &api_url)
+        .header("accept", "application/json")
+        .header("authorization", format!("Bearer {}", api_key))
+        .header("content-type", "application/json");
+    let builder = if let Some(request) = request {
         let body = serde_json::to_string_pretty(&request)?;
         // todo
         log::info!("Body: [{}]", body);
         println!("Body: [{}]", body);
-        reqwest::Client::new()
-            .post(api_url)
-            .header("accept", "application/json")
-            .header("authorization", format!("Bearer {}", api_key))
-            .header("content-type", "application/json")
-            .body(body)
-            .send()
-            .await?
-            .json::<Response>()
-            .await?
+        builder.body(body)
     } else {
-        reqwest::Client::new()
-            .post(api_url)
-            .header("accept", "application/json")
-            .header("authorization", format!("Bearer {}", api_key))
-            .header("content-type", "application/json")
-            .send()
-            .await?
-            .json::<Response>()
-            .await?
+        builder
     };
-    Ok(response)
+    let response = builder.send().await?;
+    let status = response.status();
+    let text = response.text().await?;
+    if !status.is_success() {
+        anyhow::bail!(
+            "{} {} failed with {}: {}",
+            method,
+            api_url,
+            status,
+            text.trim()
+        );
+    }
+    let text = if text.trim().is_empty() {
+        "null"
+    } else {
+        &text
+    };
+    Ok(serde_json::from_str::<Response>(text)?)
+}
+
+/// Page through an endpoint. `fetch` is called with the pagination token of the previous page,
+/// empty for the first one, and returns the rows and pagination token of its page.
+pub(crate) async fn fetch_all_pages<Row, Fut>(
+    mut fetch: impl FnMut(String) -> Fut,
+) -> anyhow::Result<Vec<Row>>
+where
+    Fut: std::future::Future<Output = anyhow::Result<(Option<Vec<Row>>, Option<String>)>>,
+{
+    let mut rows = vec![];
+    let mut pagination_token = String::new();
+    loop {
+        let (data, next_token) = fetch(pagination_token).await?;
+        let data = data.unwrap_or_default();
+        let is_last_page = data.is_empty();
+        rows.extend(data);
+        match next_token {
+            Some(token) if !is_last_page && !token.is_empty() => pagination_token = token,
+            _ => break,
+        }
+    }
+    Ok(rows)
 }
 
 pub fn limit_is_zero(value: &usize) -> bool {
@@ -58,3 +102,77 @@ pub fn pretty_json(value: serde_json::Value) -> String {
     use serde_json::json;
     format!("{:#}", json!(value))
 }
+
+/// 1 SOL is 1,000,000,000 lamports since SOL has 9 decimals.
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Parse an amount denoted in SOL into lamports, e.g. the price of a secondary sale.
+///
+/// Integers are SOL too: `"2"` is 2 SOL.
+pub fn parse_sol(value: &str) -> Option<u64> {
+    let sol = value.trim().parse::<f64>().ok()?;
+    (sol.is_finite() && sol >= 0.0).then(|| (sol * LAMPORTS_PER_SOL as f64).round() as u64)
+}
+
+pub fn lamports_to_sol(lamports: u64) -> f64 {
+    lamports as f64 / LAMPORTS_PER_SOL as f64
+}
+
+pub fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Deserialize `tests/fixtures/{name}.json`, a response body as returned by the api.
+#[cfg(test)]
+pub(crate) fn fixture<T: DeserializeOwned>(name: &str) -> T {
+    let path = format!(
+        "{}/tests/fixtures/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    let json = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    serde_json::from_str(&json).unwrap_or_else(|e| panic!("{}: {}", path, e))
+}
+
+#[test]
+fn test_parse_sol() {
+    assert_eq!(parse_sol("2"), Some(2_000_000_000));
+    assert_eq!(parse_sol(" 1.25 "), Some(1_250_000_000));
+    assert_eq!(parse_sol("-1"), None);
+    assert_eq!(parse_sol("NaN"), None);
+}
+
+#[tokio::test]
+async fn test_core_call_with_method_error_status() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let api_url = format!(
+        "http://{}/v1/subscriptions/id",
+        listener.local_addr().unwrap()
+    );
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buffer = [0; 4096];
+        let _ = socket.read(&mut buffer).await.unwrap();
+        let body = r#"{"message":"Unauthorized"}"#;
+        let response = format!(
+            "HTTP/1.1 401 Unauthorized\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+    });
+
+    let error = core_call_with_method::<(), serde_json::Value>(
+        reqwest::Method::DELETE,
+        None,
+        api_url,
+        "api_key",
+    )
+    .await
+    .unwrap_err()
+    .to_string();
+    assert!(error.contains("401"), "{}", error);
+    assert!(error.contains("Unauthorized"), "{}", error);
+}
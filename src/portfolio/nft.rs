@@ -0,0 +1,234 @@
+//! # NFT Portfolio
+//!
+//! Values every NFT held by a wallet.
+//!
+//! The mints of the wallet are paged through with the Mints By Owner endpoint and grouped by
+//! helloMoonCollectionId. Each group is valued twice:
+//!
+//! - at floor: every mint is worth the latest floor price of its collection
+//! - estimated: every mint is worth its estimated price, or the floor price when it has none
+//!
+//! Mints without a helloMoonCollectionId or without any price are counted but not valued.
+use std::collections::{BTreeMap, HashMap};
+
+use crate::dimension_facts::nft::latest_floor_price::{
+    latest_floor_price, LatestFloorPriceRequest,
+};
+use crate::dimension_facts::nft::mints_by_owner::{
+    mints_by_owners, MintsByOwner, MintsByOwnerRequest,
+};
+use crate::dimension_facts::nft::nft_estimated_price::{
+    nft_estimated_price, NftEstimatedPriceRequest,
+};
+use crate::fetch_all_pages;
+use crate::lamports_to_sol;
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+
+/// Value of the mints of one collection held by the wallet. Values are in SOL.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionValuation {
+    /// `None` groups the mints Hello Moon could not map to a collection
+    pub hello_moon_collection_id: Option<String>,
+    pub nft_mints: Vec<String>,
+    pub floor_price: Option<f64>,
+    pub floor_value: f64,
+    pub estimated_value: f64,
+    /// Number of mints without an estimated price nor a floor price
+    pub unvalued_mints: usize,
+}
+
+/// Value of every NFT held by a wallet. Values are in SOL.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct NftPortfolio {
+    pub owner_account: String,
+    /// Sorted by estimated value, highest first
+    pub collections: Vec<CollectionValuation>,
+    pub nft_count: usize,
+    pub floor_value: f64,
+    pub estimated_value: f64,
+}
+
+/// Value `mints` with already fetched prices.
+///
+/// `floor_prices` is keyed by helloMoonCollectionId and `estimated_prices` by nftMint, both in lamports.
+pub fn value_nft_portfolio(
+    owner_account: &str,
+    mints: &[MintsByOwner],
+    floor_prices: &HashMap<String, u64>,
+    estimated_prices: &HashMap<String, u64>,
+) -> NftPortfolio {
+    let mut groups: BTreeMap<Option<&str>, Vec<&str>> = BTreeMap::new();
+    for mint in mints {
+        if let Some(nft_mint) = mint.nft_mint.as_deref() {
+            groups
+                .entry(mint.hello_moon_collection_id.as_deref())
+                .or_default()
+                .push(nft_mint);
+        }
+    }
+
+    let mut portfolio = NftPortfolio {
+        owner_account: owner_account.to_string(),
+        ..Default::default()
+    };
+    for (hello_moon_collection_id, nft_mints) in groups {
+        let floor_price = hello_moon_collection_id.and_then(|id| floor_prices.get(id).copied());
+        let mut valuation = CollectionValuation {
+            hello_moon_collection_id: hello_moon_collection_id.map(str::to_string),
+            nft_mints: nft_mints
+                .iter()
+                .map(|nft_mint| nft_mint.to_string())
+                .collect(),
+            floor_price: floor_price.map(lamports_to_sol),
+            ..Default::default()
+        };
+        for nft_mint in nft_mints {
+            let estimated = estimated_prices.get(nft_mint).copied().or(floor_price);
+            valuation.floor_value += floor_price.map(lamports_to_sol).unwrap_or_default();
+            valuation.estimated_value += estimated.map(lamports_to_sol).unwrap_or_default();
+            valuation.unvalued_mints += usize::from(estimated.is_none());
+        }
+
+        portfolio.nft_count += valuation.nft_mints.len();
+        portfolio.floor_value += valuation.floor_value;
+        portfolio.estimated_value += valuation.estimated_value;
+        portfolio.collections.push(valuation);
+    }
+    portfolio
+        .collections
+        .sort_by(|a, b| b.estimated_value.total_cmp(&a.estimated_value));
+    portfolio
+}
+
+/// Number of estimated price requests in flight at once.
+const ESTIMATED_PRICE_CONCURRENCY: usize = 8;
+
+/// Fetch every NFT held by `owner_account` with its floor and estimated price and value them.
+///
+/// A price that fails to be fetched is logged and treated as missing: the mint falls back to the
+/// floor price of its collection, or is counted as unvalued, instead of failing the portfolio.
+pub async fn nft_portfolio(api_key: &str, owner_account: &str) -> anyhow::Result<NftPortfolio> {
+    let mints = fetch_all_pages(|pagination_token| {
+        let request = MintsByOwnerRequest {
+            owner_account: owner_account.to_string(),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = mints_by_owners(api_key, Some(request)).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+
+    let mut floor_prices = HashMap::new();
+    for mint in &mints {
+        let Some(id) = mint.hello_moon_collection_id.as_deref() else {
+            continue;
+        };
+        if floor_prices.contains_key(id) {
+            continue;
+        }
+        let request = LatestFloorPriceRequest {
+            hello_moon_collection_id: id.to_string(),
+            ..Default::default()
+        };
+        let response = match latest_floor_price(api_key, Some(request)).await {
+            Ok(response) => response,
+            Err(e) => {
+                log::warn!("failed to fetch the floor price of {}: {}", id, e);
+                continue;
+            }
+        };
+        if let Some(floor_price) = response
+            .data
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|row| row.floor_price)
+        {
+            floor_prices.insert(id.to_string(), floor_price);
+        }
+    }
+
+    let estimated_prices: HashMap<String, u64> =
+        stream::iter(mints.iter().filter_map(|mint| mint.nft_mint.as_deref()))
+            .map(|nft_mint| async move {
+                let request = NftEstimatedPriceRequest {
+                    nft_mint: nft_mint.to_string(),
+                    ..Default::default()
+                };
+                match nft_estimated_price(api_key, Some(request)).await {
+                    Ok(response) => response
+                        .data
+                        .unwrap_or_default()
+                        .into_iter()
+                        .find_map(|row| row.estimated_price)
+                        .map(|estimated_price| (nft_mint.to_string(), estimated_price)),
+                    Err(e) => {
+                        log::warn!("failed to fetch the estimated price of {}: {}", nft_mint, e);
+                        None
+                    }
+                }
+            })
+            .buffer_unordered(ESTIMATED_PRICE_CONCURRENCY)
+            .filter_map(|estimated_price| async move { estimated_price })
+            .collect()
+            .await;
+
+    Ok(value_nft_portfolio(
+        owner_account,
+        &mints,
+        &floor_prices,
+        &estimated_prices,
+    ))
+}
+
+#[test]
+fn test_value_nft_portfolio() {
+    let mint = |nft_mint: &str, collection: Option<&str>| MintsByOwner {
+        nft_mint: Some(nft_mint.to_string()),
+        hello_moon_collection_id: collection.map(str::to_string),
+        ..Default::default()
+    };
+    let mints = vec![
+        mint("a1", Some("a")),
+        mint("a2", Some("a")),
+        mint("b1", Some("b")),
+        mint("c1", Some("c")),
+        mint("x", None),
+    ];
+    let floor_prices = HashMap::from([
+        ("a".to_string(), 2_000_000_000),
+        ("b".to_string(), 10_000_000_000),
+    ]);
+    let estimated_prices = HashMap::from([("a1".to_string(), 3_000_000_000)]);
+
+    let portfolio = value_nft_portfolio("owner", &mints, &floor_prices, &estimated_prices);
+
+    assert_eq!(portfolio.nft_count, 5);
+    assert_eq!(portfolio.floor_value, 14.0);
+    assert_eq!(portfolio.estimated_value, 15.0);
+    let ids: Vec<Option<&str>> = portfolio
+        .collections
+        .iter()
+        .map(|collection| collection.hello_moon_collection_id.as_deref())
+        .collect();
+    assert_eq!(ids, vec![Some("b"), Some("a"), None, Some("c")]);
+    let a = &portfolio.collections[1];
+    assert_eq!(a.floor_price, Some(2.0));
+    assert_eq!(a.floor_value, 4.0);
+    assert_eq!(a.estimated_value, 5.0);
+    assert_eq!(portfolio.collections[2].unvalued_mints, 1);
+    assert_eq!(portfolio.collections[3].unvalued_mints, 1);
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_nft_portfolio() {
+    let api_key = dotenv::var("api_keys").unwrap();
+    let owner_account = dotenv::var("owner_account").unwrap();
+    let portfolio = nft_portfolio(&api_key, &owner_account).await.unwrap();
+
+    println!("{:#?}", portfolio);
+}
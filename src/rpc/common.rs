@@ -0,0 +1,275 @@
+//! # Solana JSON-RPC
+//!
+//! POST `https://rpc.hellomoon.io`
+//!
+//! The Hello Moon RPC nodes serve the standard Solana JSON-RPC api, authenticated with the same
+//! api key as the rest api. Every call is a JSON-RPC 2.0 request with positional params:
+//!
+//! ```json
+//! { "jsonrpc": "2.0", "id": 1, "method": "getBalance", "params": ["<pubkey>"] }
+//! ```
+//!
+//! [`rpc_call`] sends it and returns the `result` of the response, or the `error` as an
+//! [`RpcError`]. Types shared by several methods are defined here.
+use crate::core_call;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub const HELLOMOON_RPC_URL: &str = "https://rpc.hellomoon.io";
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct RpcRequest<Params> {
+    pub jsonrpc: String,
+    pub id: u64,
+    pub method: String,
+    pub params: Params,
+}
+
+impl<Params> RpcRequest<Params> {
+    pub fn new(method: impl Into<String>, params: Params) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: 1,
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct RpcResponse {
+    pub jsonrpc: Option<String>,
+    pub id: Option<u64>,
+    /// Set when the call succeeded, `null` is a valid result of some methods
+    pub result: Option<serde_json::Value>,
+    /// Set when the call failed
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    /// The result deserialized into `Result`, or the error of the node.
+    pub fn into_result<Result: DeserializeOwned>(self) -> anyhow::Result<Result> {
+        if let Some(error) = self.error {
+            return Err(error.into());
+        }
+        let result = self.result.unwrap_or(serde_json::Value::Null);
+        Ok(serde_json::from_value(result)?)
+    }
+}
+
+/// An error returned by the node, e.g. `-32602` for invalid params.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "rpc error {}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+/// Call `method` on the Hello Moon RPC nodes.
+pub async fn rpc_call<Params: Serialize, Result: DeserializeOwned>(
+    method: &str,
+    params: Params,
+    api_key: &str,
+) -> anyhow::Result<Result> {
+    rpc_call_with_url(HELLOMOON_RPC_URL, method, params, api_key).await
+}
+
+/// Same as `rpc_call` against another node, e.g. a dedicated one.
+pub async fn rpc_call_with_url<Params: Serialize, Result: DeserializeOwned>(
+    rpc_url: &str,
+    method: &str,
+    params: Params,
+    api_key: &str,
+) -> anyhow::Result<Result> {
+    let request = RpcRequest::new(method, params);
+    core_call::<RpcRequest<Params>, RpcResponse>(Some(request), rpc_url.to_string(), api_key)
+        .await?
+        .into_result()
+}
+
+/// How final the state a call is evaluated against must be.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Commitment {
+    /// The latest block the node has voted on, it may be skipped by the cluster
+    #[serde(rename = "processed")]
+    Processed,
+    /// The latest block voted on by a supermajority of the cluster
+    #[serde(rename = "confirmed")]
+    Confirmed,
+    /// The latest block rooted by a supermajority of the cluster
+    #[default]
+    #[serde(rename = "finalized")]
+    Finalized,
+}
+
+/// The encoding of account data and transactions.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum Encoding {
+    #[serde(rename = "base58")]
+    Base58,
+    #[serde(rename = "base64")]
+    Base64,
+    #[serde(rename = "base64+zstd")]
+    Base64Zstd,
+    #[serde(rename = "json")]
+    Json,
+    /// Decoded by the node when it knows the program owning the data, base64 otherwise
+    #[serde(rename = "jsonParsed")]
+    JsonParsed,
+}
+
+/// The configuration of the methods only taking a commitment.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct RpcContextConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
+    /// The minimum slot the request can be evaluated at
+    #[serde(rename = "minContextSlot")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_context_slot: Option<u64>,
+}
+
+/// The configuration of the methods returning accounts.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct RpcAccountInfoConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
+    /// Encoding of the account data, base58 by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<Encoding>,
+    /// Only return `length` bytes of the data from `offset`, for the binary encodings
+    #[serde(rename = "dataSlice")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_slice: Option<DataSlice>,
+    /// The minimum slot the request can be evaluated at
+    #[serde(rename = "minContextSlot")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_context_slot: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct DataSlice {
+    pub offset: usize,
+    pub length: usize,
+}
+
+/// The slot a result was evaluated at.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct RpcContext {
+    pub slot: u64,
+    #[serde(rename = "apiVersion")]
+    pub api_version: Option<String>,
+}
+
+/// The result of the methods returning a value along with the slot it was read at.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct WithContext<T> {
+    pub context: RpcContext,
+    pub value: T,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Account {
+    /// Balance of the account, in lamports
+    pub lamports: u64,
+    /// Base-58 encoded PublicKey of the program owning the account
+    pub owner: String,
+    pub data: AccountData,
+    /// Whether the account holds a program
+    pub executable: bool,
+    /// The epoch the account will next owe rent at
+    #[serde(rename = "rentEpoch")]
+    pub rent_epoch: Option<u64>,
+    /// Size of the data, in bytes
+    pub space: Option<u64>,
+}
+
+/// The data of an account in the encoding requested.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum AccountData {
+    /// The encoded data and its encoding
+    Binary(String, Encoding),
+    /// The data decoded by the node, with the `jsonParsed` encoding
+    Parsed(ParsedAccountData),
+    /// Base-58 encoded data, returned when no encoding is requested
+    LegacyBinary(String),
+}
+
+impl Default for AccountData {
+    fn default() -> Self {
+        AccountData::LegacyBinary(String::new())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct ParsedAccountData {
+    /// Name of the program owning the account, e.g. `spl-token`
+    pub program: String,
+    /// The account state, whose shape depends on the program
+    pub parsed: serde_json::Value,
+    pub space: u64,
+}
+
+/// An account along with its address.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct KeyedAccount {
+    /// Base-58 encoded PublicKey of the account
+    pub pubkey: String,
+    pub account: Account,
+}
+
+#[test]
+fn test_rpc_response_into_result() {
+    let response: RpcResponse =
+        serde_json::from_str(r#"{"jsonrpc": "2.0", "result": 42, "id": 1}"#).unwrap();
+    assert_eq!(response.into_result::<u64>().unwrap(), 42);
+
+    let response: RpcResponse =
+        serde_json::from_str(r#"{"jsonrpc": "2.0", "result": null, "id": 1}"#).unwrap();
+    assert_eq!(response.into_result::<Option<u64>>().unwrap(), None);
+
+    let response: RpcResponse = serde_json::from_str(
+        r#"{"jsonrpc": "2.0", "error": {"code": -32602, "message": "Invalid param"}, "id": 1}"#,
+    )
+    .unwrap();
+    let error = response.into_result::<u64>().unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<RpcError>().map(|error| error.code),
+        Some(-32602)
+    );
+}
+
+#[test]
+fn test_account_data_serde() {
+    let account: Account = serde_json::from_value(serde_json::json!({
+        "lamports": 2039280,
+        "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+        "data": ["AQID", "base64"],
+        "executable": false,
+        "rentEpoch": 18446744073709551615u64,
+        "space": 165
+    }))
+    .unwrap();
+    assert_eq!(
+        account.data,
+        AccountData::Binary("AQID".to_string(), Encoding::Base64)
+    );
+    assert_eq!(account.rent_epoch, Some(u64::MAX));
+
+    let data: AccountData = serde_json::from_value(serde_json::json!({
+        "program": "spl-token",
+        "parsed": { "type": "account" },
+        "space": 165
+    }))
+    .unwrap();
+    assert!(matches!(data, AccountData::Parsed(parsed) if parsed.program == "spl-token"));
+}
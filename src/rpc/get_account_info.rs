@@ -0,0 +1,58 @@
+//! # getAccountInfo
+//!
+//! The account at an address, `None` when it does not exist.
+use super::common::{rpc_call, Account, RpcAccountInfoConfig, WithContext};
+
+pub async fn get_account_info(
+    pubkey: &str,
+    config: Option<RpcAccountInfoConfig>,
+    api_key: &str,
+) -> anyhow::Result<WithContext<Option<Account>>> {
+    rpc_call("getAccountInfo", (pubkey, config), api_key).await
+}
+
+#[test]
+fn test_get_account_info_params() {
+    use super::common::{Encoding, RpcRequest};
+
+    let config = RpcAccountInfoConfig {
+        encoding: Some(Encoding::Base64),
+        ..Default::default()
+    };
+    let request = RpcRequest::new("getAccountInfo", ("pubkey", Some(config)));
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getAccountInfo",
+            "params": ["pubkey", { "encoding": "base64" }]
+        })
+    );
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_get_account_info() {
+    use super::common::Encoding;
+
+    let config = RpcAccountInfoConfig {
+        encoding: Some(Encoding::JsonParsed),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = get_account_info(
+        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+        Some(config),
+        &api_key,
+    )
+    .await
+    .unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: WithContext<Option<Account>> = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
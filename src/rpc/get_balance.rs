@@ -0,0 +1,39 @@
+//! # getBalance
+//!
+//! Balance of an account, in lamports.
+use super::common::{rpc_call, RpcContextConfig, WithContext};
+use crate::lamports_to_sol;
+
+pub async fn get_balance(
+    pubkey: &str,
+    config: Option<RpcContextConfig>,
+    api_key: &str,
+) -> anyhow::Result<WithContext<u64>> {
+    rpc_call("getBalance", (pubkey, config), api_key).await
+}
+
+/// `get_balance` in SOL.
+pub async fn get_balance_sol(pubkey: &str, api_key: &str) -> anyhow::Result<f64> {
+    Ok(lamports_to_sol(
+        get_balance(pubkey, None, api_key).await?.value,
+    ))
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_get_balance() {
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = get_balance(
+        "83astBRguLMdt2h5U1Tpdq5tjFoJ6noeGwaY3mDLVcri",
+        None,
+        &api_key,
+    )
+    .await
+    .unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: WithContext<u64> = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
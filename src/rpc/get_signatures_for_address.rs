@@ -0,0 +1,109 @@
+//! # getSignaturesForAddress
+//!
+//! Signatures of the confirmed transactions involving an address, newest first. Older
+//! signatures are paginated with `before`.
+use super::common::{rpc_call, Commitment};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct GetSignaturesForAddressConfig {
+    /// Maximum number of signatures to return, between 1 and 1000 (default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Start searching backwards from this signature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Stop searching at this signature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
+    /// The minimum slot the request can be evaluated at
+    #[serde(rename = "minContextSlot")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_context_slot: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SignatureInfo {
+    /// Base-58 encoded first signature of the transaction
+    pub signature: String,
+    /// The slot of the block containing the transaction
+    pub slot: u64,
+    /// The error of the transaction, `None` when it succeeded
+    pub err: Option<serde_json::Value>,
+    /// The memo of the transaction
+    pub memo: Option<String>,
+    /// Unix epoch time (in seconds) of the block
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<i64>,
+    #[serde(rename = "confirmationStatus")]
+    pub confirmation_status: Option<Commitment>,
+}
+
+impl SignatureInfo {
+    pub fn is_success(&self) -> bool {
+        self.err.is_none()
+    }
+}
+
+pub async fn get_signatures_for_address(
+    address: &str,
+    config: Option<GetSignaturesForAddressConfig>,
+    api_key: &str,
+) -> anyhow::Result<Vec<SignatureInfo>> {
+    rpc_call("getSignaturesForAddress", (address, config), api_key).await
+}
+
+#[test]
+fn test_signature_info_serde() {
+    let signatures: Vec<SignatureInfo> = serde_json::from_value(serde_json::json!([
+        {
+            "signature": "5h6xBEauJ3PK6SWCZ1PGjBvj8vDdWG3KpwATGy1ARAXFSDwt8GFXM7W5Ncn16wmqokgpiKRLuS83KUxyZyv2sUYv",
+            "slot": 114,
+            "err": null,
+            "memo": null,
+            "blockTime": null,
+            "confirmationStatus": "finalized"
+        },
+        {
+            "signature": "2nBhEBYYvfaAe16UMNqRHre4YNSskvuYgx3M6E4JP1oDYvZEJHvoPzyUidNgNX5r9sTyN1J9UxtbCXy2rqYcuyuv",
+            "slot": 115,
+            "err": { "InstructionError": [0, "InvalidAccountData"] },
+            "memo": null,
+            "blockTime": 1670000000,
+            "confirmationStatus": "confirmed"
+        }
+    ]))
+    .unwrap();
+    assert!(signatures[0].is_success());
+    assert!(!signatures[1].is_success());
+    assert_eq!(
+        signatures[1].confirmation_status,
+        Some(Commitment::Confirmed)
+    );
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_get_signatures_for_address() {
+    let config = GetSignaturesForAddressConfig {
+        limit: Some(10),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = get_signatures_for_address(
+        "Vote111111111111111111111111111111111111111",
+        Some(config),
+        &api_key,
+    )
+    .await
+    .unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: Vec<SignatureInfo> = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
@@ -0,0 +1,18 @@
+//! # getSlot
+//!
+//! The slot that reached the given or default commitment level.
+use super::common::{rpc_call, RpcContextConfig};
+
+pub async fn get_slot(config: Option<RpcContextConfig>, api_key: &str) -> anyhow::Result<u64> {
+    rpc_call("getSlot", (config,), api_key).await
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_get_slot() {
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let slot = get_slot(None, &api_key).await.unwrap();
+    println!("{}", slot);
+    assert!(slot > 0);
+}
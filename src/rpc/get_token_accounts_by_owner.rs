@@ -0,0 +1,73 @@
+//! # getTokenAccountsByOwner
+//!
+//! The SPL token accounts of a wallet, of a single mint or of every mint of a token program.
+use super::common::{rpc_call, KeyedAccount, RpcAccountInfoConfig, WithContext};
+use serde::{Deserialize, Serialize};
+
+/// The program id of the SPL token program.
+pub const SPL_TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// The token accounts to return, serialized to `{ "mint": ... }` or `{ "programId": ... }`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub enum TokenAccountsFilter {
+    /// Only the accounts of this mint
+    #[serde(rename = "mint")]
+    Mint(String),
+    /// Every account owned by this token program
+    #[serde(rename = "programId")]
+    ProgramId(String),
+}
+
+impl Default for TokenAccountsFilter {
+    fn default() -> Self {
+        TokenAccountsFilter::ProgramId(SPL_TOKEN_PROGRAM_ID.to_string())
+    }
+}
+
+pub async fn get_token_accounts_by_owner(
+    owner: &str,
+    filter: TokenAccountsFilter,
+    config: Option<RpcAccountInfoConfig>,
+    api_key: &str,
+) -> anyhow::Result<WithContext<Vec<KeyedAccount>>> {
+    rpc_call("getTokenAccountsByOwner", (owner, filter, config), api_key).await
+}
+
+#[test]
+fn test_token_accounts_filter_serde() {
+    assert_eq!(
+        serde_json::to_value(TokenAccountsFilter::Mint("mint".to_string())).unwrap(),
+        serde_json::json!({ "mint": "mint" })
+    );
+    assert_eq!(
+        serde_json::to_value(TokenAccountsFilter::default()).unwrap(),
+        serde_json::json!({ "programId": SPL_TOKEN_PROGRAM_ID })
+    );
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_get_token_accounts_by_owner() {
+    use super::common::Encoding;
+
+    let config = RpcAccountInfoConfig {
+        encoding: Some(Encoding::JsonParsed),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = get_token_accounts_by_owner(
+        "83astBRguLMdt2h5U1Tpdq5tjFoJ6noeGwaY3mDLVcri",
+        TokenAccountsFilter::default(),
+        Some(config),
+        &api_key,
+    )
+    .await
+    .unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: WithContext<Vec<KeyedAccount>> = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
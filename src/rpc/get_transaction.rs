@@ -0,0 +1,167 @@
+//! # getTransaction
+//!
+//! A confirmed transaction and its status, `None` when it is not found.
+//!
+//! Versioned transactions are only returned when `max_supported_transaction_version` is set,
+//! the node answers an error otherwise.
+use super::common::{rpc_call, Commitment, Encoding};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct GetTransactionConfig {
+    /// Encoding of the transaction, json by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<Encoding>,
+    /// `processed` is not supported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
+    /// The highest transaction version to return, `0` for the versioned transactions
+    #[serde(rename = "maxSupportedTransactionVersion")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_supported_transaction_version: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct TransactionWithMeta {
+    /// The slot of the block containing the transaction
+    pub slot: u64,
+    /// Unix epoch time (in seconds) of the block
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<i64>,
+    /// The transaction in the encoding requested: a `[data, encoding]` pair for the binary
+    /// encodings, an object with the `signatures` and the `message` otherwise
+    pub transaction: serde_json::Value,
+    pub meta: Option<TransactionMeta>,
+    /// Missing when `max_supported_transaction_version` is not set
+    pub version: Option<TransactionVersion>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum TransactionVersion {
+    Number(u8),
+    /// `"legacy"`
+    Legacy(String),
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct TransactionMeta {
+    /// The error of the transaction, `None` when it succeeded
+    pub err: Option<serde_json::Value>,
+    /// Fee charged, in lamports
+    pub fee: u64,
+    /// Balances of the accounts of the transaction before it, in lamports
+    #[serde(rename = "preBalances")]
+    pub pre_balances: Vec<u64>,
+    /// Balances of the accounts of the transaction after it, in lamports
+    #[serde(rename = "postBalances")]
+    pub post_balances: Vec<u64>,
+    #[serde(rename = "preTokenBalances")]
+    pub pre_token_balances: Option<Vec<TokenBalance>>,
+    #[serde(rename = "postTokenBalances")]
+    pub post_token_balances: Option<Vec<TokenBalance>>,
+    #[serde(rename = "logMessages")]
+    pub log_messages: Option<Vec<String>>,
+    #[serde(rename = "computeUnitsConsumed")]
+    pub compute_units_consumed: Option<u64>,
+}
+
+impl TransactionMeta {
+    pub fn is_success(&self) -> bool {
+        self.err.is_none()
+    }
+
+    /// Change of the balance of the account at `account_index` in the transaction, in lamports.
+    pub fn balance_change(&self, account_index: usize) -> Option<i64> {
+        let pre = *self.pre_balances.get(account_index)?;
+        let post = *self.post_balances.get(account_index)?;
+        Some(post as i64 - pre as i64)
+    }
+}
+
+/// Balance of a token account of the transaction.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct TokenBalance {
+    /// Index of the account in the transaction
+    #[serde(rename = "accountIndex")]
+    pub account_index: usize,
+    pub mint: String,
+    /// PublicKey of the owner of the token account
+    pub owner: Option<String>,
+    #[serde(rename = "programId")]
+    pub program_id: Option<String>,
+    #[serde(rename = "uiTokenAmount")]
+    pub ui_token_amount: UiTokenAmount,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct UiTokenAmount {
+    /// Raw amount, in the smallest unit of the token
+    pub amount: String,
+    pub decimals: u8,
+    #[serde(rename = "uiAmount")]
+    pub ui_amount: Option<f64>,
+    #[serde(rename = "uiAmountString")]
+    pub ui_amount_string: Option<String>,
+}
+
+pub async fn get_transaction(
+    signature: &str,
+    config: Option<GetTransactionConfig>,
+    api_key: &str,
+) -> anyhow::Result<Option<TransactionWithMeta>> {
+    rpc_call("getTransaction", (signature, config), api_key).await
+}
+
+#[test]
+fn test_transaction_with_meta_serde() {
+    let transaction: TransactionWithMeta = serde_json::from_value(serde_json::json!({
+        "slot": 430,
+        "blockTime": null,
+        "transaction": ["AQID", "base64"],
+        "meta": {
+            "err": null,
+            "fee": 5000,
+            "preBalances": [499998937500u64, 26858640, 1],
+            "postBalances": [499998932500u64, 26858640, 1],
+            "preTokenBalances": [],
+            "postTokenBalances": [],
+            "logMessages": [],
+            "computeUnitsConsumed": 2100
+        },
+        "version": "legacy"
+    }))
+    .unwrap();
+    let meta = transaction.meta.unwrap();
+    assert!(meta.is_success());
+    assert_eq!(meta.balance_change(0), Some(-5000));
+    assert_eq!(meta.balance_change(3), None);
+    assert_eq!(
+        transaction.version,
+        Some(TransactionVersion::Legacy("legacy".to_string()))
+    );
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_get_transaction() {
+    let config = GetTransactionConfig {
+        max_supported_transaction_version: Some(0),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = get_transaction(
+        "5rfFLBUp5YPr6rC2g1KBBW8LGZBcZ8Lvs7gKAdgrBjmQvFf6EKkgc5cpAQUTwGxDJbNqtLYkjV5vS5zVK4tb6JtP",
+        Some(config),
+        &api_key,
+    )
+    .await
+    .unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: Option<TransactionWithMeta> = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
@@ -0,0 +1,7 @@
+pub mod common;
+pub mod get_account_info;
+pub mod get_balance;
+pub mod get_signatures_for_address;
+pub mod get_slot;
+pub mod get_token_accounts_by_owner;
+pub mod get_transaction;
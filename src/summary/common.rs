@@ -0,0 +1,275 @@
+//! # Summary filters
+//!
+//! Filters and results shared by the summary endpoints, which aggregate activity per day, week
+//! or month. Rows of a metric over time can be collected into a [`TimeSeries`].
+//!
+//! Days are `YYYY-MM-DD` strings. A date range serializes to the filter object the api expects:
+//!
+//! ```json
+//! { "operator": "between", "greaterThan": "2023-01-01", "lessThan": "2023-01-31" }
+//! ```
+use serde::{Deserialize, Serialize};
+
+/// The period the rows of a summary are aggregated over.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Granularity {
+    #[serde(rename = "ONE_HOUR")]
+    OneHour,
+    #[default]
+    #[serde(rename = "ONE_DAY")]
+    OneDay,
+    #[serde(rename = "ONE_WEEK")]
+    OneWeek,
+    #[serde(rename = "ONE_MONTH")]
+    OneMonth,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum RangeOperator {
+    #[serde(rename = "=")]
+    Equal,
+    #[serde(rename = ">")]
+    Greater,
+    #[serde(rename = ">=")]
+    GreaterOrEqual,
+    #[serde(rename = "<")]
+    Less,
+    #[serde(rename = "<=")]
+    LessOrEqual,
+    #[serde(rename = "between")]
+    Between,
+}
+
+/// A condition on a column of the rows, e.g. their day or block time.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct Range<T> {
+    pub operator: RangeOperator,
+    /// The value compared to, for every operator but `between`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<T>,
+    /// First value of a `between` range
+    #[serde(rename = "greaterThan")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub greater_than: Option<T>,
+    /// Last value of a `between` range
+    #[serde(rename = "lessThan")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub less_than: Option<T>,
+}
+
+/// A condition on the day of the summary rows, `YYYY-MM-DD`.
+pub type DateRange = Range<String>;
+
+/// A condition on the block time of the rows, in epoch seconds.
+pub type BlockTimeRange = Range<usize>;
+
+impl<T> Range<T> {
+    pub fn new(operator: RangeOperator, value: impl Into<T>) -> Self {
+        Self {
+            operator,
+            value: Some(value.into()),
+            greater_than: None,
+            less_than: None,
+        }
+    }
+
+    /// From `start` to `end`, both included.
+    pub fn between(start: impl Into<T>, end: impl Into<T>) -> Self {
+        Self {
+            operator: RangeOperator::Between,
+            value: None,
+            greater_than: Some(start.into()),
+            less_than: Some(end.into()),
+        }
+    }
+
+    pub fn on(value: impl Into<T>) -> Self {
+        Self::new(RangeOperator::Equal, value)
+    }
+
+    /// From `start` on, included.
+    pub fn since(start: impl Into<T>) -> Self {
+        Self::new(RangeOperator::GreaterOrEqual, start)
+    }
+
+    /// Up to `end`, included.
+    pub fn until(end: impl Into<T>) -> Self {
+        Self::new(RangeOperator::LessOrEqual, end)
+    }
+}
+
+/// A value per period, ordered by day.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct TimeSeries<T> {
+    pub granularity: Granularity,
+    pub points: Vec<TimePoint<T>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct TimePoint<T> {
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: String,
+    pub value: T,
+}
+
+impl<T> TimeSeries<T> {
+    /// Sort `points` by day, the last point of a day is kept when a day repeats.
+    pub fn new(granularity: Granularity, points: impl IntoIterator<Item = TimePoint<T>>) -> Self {
+        let mut points: Vec<TimePoint<T>> = points.into_iter().collect();
+        points.sort_by(|a, b| a.day.cmp(&b.day));
+        points.reverse();
+        points.dedup_by(|later, earlier| later.day == earlier.day);
+        points.reverse();
+        Self {
+            granularity,
+            points,
+        }
+    }
+
+    /// Build the series from summary rows, rows without a day are dropped.
+    pub fn from_rows<Row>(
+        granularity: Granularity,
+        rows: impl IntoIterator<Item = Row>,
+        point: impl Fn(Row) -> Option<(String, T)>,
+    ) -> Self {
+        Self::new(
+            granularity,
+            rows.into_iter()
+                .filter_map(point)
+                .map(|(day, value)| TimePoint { day, value }),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn get(&self, day: &str) -> Option<&T> {
+        self.points
+            .binary_search_by(|point| point.day.as_str().cmp(day))
+            .ok()
+            .map(|index| &self.points[index].value)
+    }
+
+    pub fn latest(&self) -> Option<&TimePoint<T>> {
+        self.points.last()
+    }
+
+    /// The `(day, value)` pairs in order, ready to plot.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.points
+            .iter()
+            .map(|point| (point.day.as_str(), &point.value))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> TimeSeries<U> {
+        TimeSeries {
+            granularity: self.granularity,
+            points: self
+                .points
+                .iter()
+                .map(|point| TimePoint {
+                    day: point.day.clone(),
+                    value: f(&point.value),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TimeSeries<u64> {
+    pub fn total(&self) -> u64 {
+        self.points.iter().map(|point| point.value).sum()
+    }
+
+    /// Running total of the series — e.g. the total number of users from daily new users.
+    pub fn cumulative(&self) -> TimeSeries<u64> {
+        let mut total = 0;
+        self.map(|value| {
+            total += value;
+            total
+        })
+    }
+}
+
+impl TimeSeries<f64> {
+    pub fn total(&self) -> f64 {
+        self.points.iter().map(|point| point.value).sum()
+    }
+
+    /// Ratio of each value to the value of `total` on the same day — e.g. a program's share of
+    /// the volume of a market. Days missing from `total` or where it is zero are dropped.
+    pub fn share_of(&self, total: &TimeSeries<f64>) -> TimeSeries<f64> {
+        TimeSeries {
+            granularity: self.granularity,
+            points: self
+                .points
+                .iter()
+                .filter_map(|point| {
+                    let total = *total.get(&point.day)?;
+                    (total != 0.0).then(|| TimePoint {
+                        day: point.day.clone(),
+                        value: point.value / total,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+#[test]
+fn test_time_series() {
+    let series = TimeSeries::from_rows(
+        Granularity::OneDay,
+        vec![
+            (Some("2023-01-03"), 5),
+            (None, 100),
+            (Some("2023-01-01"), 1),
+            (Some("2023-01-02"), 2),
+            (Some("2023-01-02"), 3),
+        ],
+        |(day, value)| Some((day?.to_string(), value)),
+    );
+
+    assert_eq!(
+        series.iter().collect::<Vec<_>>(),
+        vec![("2023-01-01", &1), ("2023-01-02", &3), ("2023-01-03", &5)]
+    );
+    assert_eq!(series.get("2023-01-02"), Some(&3));
+    assert_eq!(series.get("2023-01-04"), None);
+    assert_eq!(series.total(), 9);
+    assert_eq!(series.cumulative().latest().unwrap().value, 9);
+    assert_eq!(series.cumulative().get("2023-01-02"), Some(&4));
+}
+
+#[test]
+fn test_date_range_serde() {
+    assert_eq!(
+        serde_json::to_value(DateRange::between("2023-01-01", "2023-01-31")).unwrap(),
+        serde_json::json!({
+            "operator": "between",
+            "greaterThan": "2023-01-01",
+            "lessThan": "2023-01-31"
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(DateRange::since("2023-01-01")).unwrap(),
+        serde_json::json!({ "operator": ">=", "value": "2023-01-01" })
+    );
+    assert_eq!(
+        serde_json::to_value(BlockTimeRange::between(1673226666usize, 1673831466usize)).unwrap(),
+        serde_json::json!({
+            "operator": "between",
+            "greaterThan": 1673226666,
+            "lessThan": 1673831466
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(Granularity::OneWeek).unwrap(),
+        serde_json::json!("ONE_WEEK")
+    );
+}
@@ -1,45 +1,86 @@
-use serde::{Deserialize, Serialize};
-
+//! # Jupiter Current Stats
+//!
+//! POST `https://rest-api.hellomoon.io/v0/jupiter/current-stats`
+//!
+//! Volume, swaps and users routed through Jupiter over the last 24 hours and 7 days.
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct JupiterCurrentStatsRequest {
+    /// The aggregator program version — e.g. `Jupiter v4`, every version when empty
+    #[serde(rename = "aggregatorName")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub aggregator_name: String,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct JupiterCurrentStatsResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<JupiterCurrentStats>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct JupiterCurrentStats {
+    /// The aggregator program version — e.g. `Jupiter v4`
+    #[serde(rename = "aggregatorName")]
+    pub aggregator_name: Option<String>,
+    /// Volume swapped in the last 24 hours, in USD
+    #[serde(rename = "volumeUsd24h")]
+    pub volume_usd_24h: Option<f64>,
+    /// Volume swapped in the last 7 days, in USD
+    #[serde(rename = "volumeUsd7d")]
+    pub volume_usd_7d: Option<f64>,
+    /// Number of swaps in the last 24 hours
+    #[serde(rename = "swapCount24h")]
+    pub swap_count_24h: Option<u64>,
+    /// Number of swaps in the last 7 days
+    #[serde(rename = "swapCount7d")]
+    pub swap_count_7d: Option<u64>,
+    /// Number of distinct accounts that swapped in the last 24 hours
+    #[serde(rename = "uniqueUsers24h")]
+    pub unique_users_24h: Option<u64>,
+    /// Number of distinct accounts that swapped in the last 7 days
+    #[serde(rename = "uniqueUsers7d")]
+    pub unique_users_7d: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+pub async fn jupiter_current_stats(
+    request: Option<JupiterCurrentStatsRequest>,
+    api_key: &str,
+) -> anyhow::Result<JupiterCurrentStatsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/jupiter/current-stats");
+    core_call::<JupiterCurrentStatsRequest, JupiterCurrentStatsResponse>(request, api_url, api_key)
+        .await
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_jupiter_current_stats() {
+    let request = JupiterCurrentStatsRequest::default();
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = jupiter_current_stats(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: JupiterCurrentStatsResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
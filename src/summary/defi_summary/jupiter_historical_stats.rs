@@ -1,45 +1,163 @@
-use serde::{Deserialize, Serialize};
-
+//! # Jupiter Historical Stats
+//!
+//! POST `https://rest-api.hellomoon.io/v0/jupiter/historical-stats`
+//!
+//! Volume, swaps and users routed through Jupiter per day, week or month.
+//!
+//! To benchmark the share of Jupiter volume routed by a program, collect its own volume into a
+//! `TimeSeries<f64>` and compare it with [`TimeSeries::share_of`] to the `volume_usd` of
+//! [`jupiter_historical_time_series`].
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct JupiterHistoricalStatsRequest {
+    /// The aggregator program version — e.g. `Jupiter v4`, every version when empty
+    #[serde(rename = "aggregatorName")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub aggregator_name: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the stats are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct JupiterHistoricalStatsResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<JupiterHistoricalStats>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct JupiterHistoricalStats {
+    /// The aggregator program version — e.g. `Jupiter v4`
+    #[serde(rename = "aggregatorName")]
+    pub aggregator_name: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Volume swapped in the period, in USD
+    #[serde(rename = "volumeUsd")]
+    pub volume_usd: Option<f64>,
+    /// Number of swaps in the period
+    #[serde(rename = "swapCount")]
+    pub swap_count: Option<u64>,
+    /// Number of distinct accounts that swapped in the period
+    #[serde(rename = "uniqueUsers")]
+    pub unique_users: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+pub async fn jupiter_historical_stats(
+    request: Option<JupiterHistoricalStatsRequest>,
+    api_key: &str,
+) -> anyhow::Result<JupiterHistoricalStatsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/jupiter/historical-stats");
+    core_call::<JupiterHistoricalStatsRequest, JupiterHistoricalStatsResponse>(
+        request, api_url, api_key,
+    )
+    .await
+}
+
+/// Every stats row matching `day`, as a series.
+pub async fn jupiter_historical_time_series(
+    api_key: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<JupiterHistoricalStats>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = JupiterHistoricalStatsRequest {
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = jupiter_historical_stats(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(TimeSeries::from_rows(granularity, rows, |row| {
+        Some((row.day.clone()?, row))
+    }))
+}
+
+#[test]
+fn test_jupiter_routing_share() {
+    use crate::summary::common::TimePoint;
+
+    let jupiter = TimeSeries::from_rows(
+        Granularity::OneDay,
+        vec![
+            JupiterHistoricalStats {
+                day: Some("2023-01-01".to_string()),
+                volume_usd: Some(1000.0),
+                ..Default::default()
+            },
+            JupiterHistoricalStats {
+                day: Some("2023-01-02".to_string()),
+                volume_usd: Some(2000.0),
+                ..Default::default()
+            },
+        ],
+        |row| Some((row.day.clone()?, row)),
+    );
+    let ours = TimeSeries::new(
+        Granularity::OneDay,
+        vec![
+            TimePoint {
+                day: "2023-01-01".to_string(),
+                value: 100.0,
+            },
+            TimePoint {
+                day: "2023-01-02".to_string(),
+                value: 500.0,
+            },
+        ],
+    );
+
+    let share = ours.share_of(&jupiter.map(|row| row.volume_usd.unwrap_or_default()));
+
+    assert_eq!(
+        share.iter().collect::<Vec<_>>(),
+        vec![("2023-01-01", &0.1), ("2023-01-02", &0.25)]
+    );
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_jupiter_historical_stats() {
+    let request = JupiterHistoricalStatsRequest {
+        day: Some(DateRange::since("2023-01-01")),
+        granularity: Some(Granularity::OneWeek),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = jupiter_historical_stats(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: JupiterHistoricalStatsResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,45 +1,100 @@
-use serde::{Deserialize, Serialize};
-
+//! # Jupiter Pair Volume
+//!
+//! POST `https://rest-api.hellomoon.io/v0/jupiter/pair-volume`
+//!
+//! Volume swapped through Jupiter per token pair, per day, week or month. A pair is directed:
+//! swaps from `sourceMint` to `destinationMint` are counted apart from the opposite direction.
+use crate::summary::common::{DateRange, Granularity};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct JupiterPairVolumeRequest {
+    /// Mint of the token sent to Jupiter
+    #[serde(rename = "sourceMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub source_mint: String,
+    /// Mint of the token received from Jupiter
+    #[serde(rename = "destinationMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub destination_mint: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the volume is aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct JupiterPairVolumeResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<JupiterPairVolume>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct JupiterPairVolume {
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Mint of the token sent to Jupiter
+    #[serde(rename = "sourceMint")]
+    pub source_mint: Option<String>,
+    /// Symbol of the token sent per our spl_token_list metadata
+    #[serde(rename = "sourceSymbol")]
+    pub source_symbol: Option<String>,
+    /// Mint of the token received from Jupiter
+    #[serde(rename = "destinationMint")]
+    pub destination_mint: Option<String>,
+    /// Symbol of the token received per our spl_token_list metadata
+    #[serde(rename = "destinationSymbol")]
+    pub destination_symbol: Option<String>,
+    /// Volume swapped in the period, in USD
+    #[serde(rename = "volumeUsd")]
+    pub volume_usd: Option<f64>,
+    /// Number of swaps in the period
+    #[serde(rename = "swapCount")]
+    pub swap_count: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+pub async fn jupiter_pair_volume(
+    request: Option<JupiterPairVolumeRequest>,
+    api_key: &str,
+) -> anyhow::Result<JupiterPairVolumeResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/jupiter/pair-volume");
+    core_call::<JupiterPairVolumeRequest, JupiterPairVolumeResponse>(request, api_url, api_key)
+        .await
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_jupiter_pair_volume() {
+    let request = JupiterPairVolumeRequest {
+        source_mint: "So11111111111111111111111111111111111111112".to_string(),
+        destination_mint: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+        day: Some(DateRange::between("2023-01-01", "2023-01-31")),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = jupiter_pair_volume(Some(request), &api_key).await.unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: JupiterPairVolumeResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
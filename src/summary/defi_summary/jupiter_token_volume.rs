@@ -1,45 +1,95 @@
-use serde::{Deserialize, Serialize};
-
+//! # Jupiter Token Volume
+//!
+//! POST `https://rest-api.hellomoon.io/v0/jupiter/token-volume`
+//!
+//! Volume of a token bought and sold through Jupiter, per day, week or month.
+use crate::summary::common::{DateRange, Granularity};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct JupiterTokenVolumeRequest {
+    /// Mint address of the token per the SPL token program
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub mint: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the volume is aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct JupiterTokenVolumeResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<JupiterTokenVolume>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct JupiterTokenVolume {
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Mint address of the token per the SPL token program
+    pub mint: Option<String>,
+    /// Symbol of the token per our spl_token_list metadata
+    pub symbol: Option<String>,
+    /// Volume of the token received from Jupiter, in USD
+    #[serde(rename = "buyVolumeUsd")]
+    pub buy_volume_usd: Option<f64>,
+    /// Volume of the token sent to Jupiter, in USD
+    #[serde(rename = "sellVolumeUsd")]
+    pub sell_volume_usd: Option<f64>,
+    /// Number of swaps sending or receiving the token
+    #[serde(rename = "swapCount")]
+    pub swap_count: Option<u64>,
+}
+
+impl JupiterTokenVolume {
+    /// Volume bought and sold, in USD.
+    pub fn volume_usd(&self) -> f64 {
+        self.buy_volume_usd.unwrap_or_default() + self.sell_volume_usd.unwrap_or_default()
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+pub async fn jupiter_token_volume(
+    request: Option<JupiterTokenVolumeRequest>,
+    api_key: &str,
+) -> anyhow::Result<JupiterTokenVolumeResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/jupiter/token-volume");
+    core_call::<JupiterTokenVolumeRequest, JupiterTokenVolumeResponse>(request, api_url, api_key)
+        .await
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_jupiter_token_volume() {
+    let request = JupiterTokenVolumeRequest {
+        mint: "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263".to_string(),
+        day: Some(DateRange::since("2023-01-01")),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = jupiter_token_volume(Some(request), &api_key).await.unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: JupiterTokenVolumeResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,45 +1,82 @@
-use serde::{Deserialize, Serialize};
-
+//! # Program New Users
+//!
+//! POST `https://rest-api.hellomoon.io/v0/defi/program/new-users`
+//!
+//! Number of accounts calling a program for the first time, per day, week or month.
+use crate::summary::common::{DateRange, Granularity};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {}
+pub struct ProgramNewUsersRequest {
+    /// The public key (address) of the account containing the program on chain.
+    /// > You can also visit https://www.hellomoon.io/id?search=program to search for a program using a user interface.
+    #[serde(rename = "programId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub program_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the new users are counted over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct ProgramNewUsersResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<ProgramNewUsers>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {
-    #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
-    #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
-    #[serde(rename = "paginationToken")]
-    #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct ProgramNewUsers {
+    /// The public key (address) of the account containing the program on chain.
+    #[serde(rename = "programId")]
+    pub program_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of accounts whose first transaction calling the program is in the period
+    #[serde(rename = "newUsers")]
+    pub new_users: Option<u64>,
 }
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+
+pub async fn program_new_users(
+    request: Option<ProgramNewUsersRequest>,
+    api_key: &str,
+) -> anyhow::Result<ProgramNewUsersResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/defi/program/new-users");
+    core_call::<ProgramNewUsersRequest, ProgramNewUsersResponse>(request, api_url, api_key).await
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_program_new_users() {
+    let request = ProgramNewUsersRequest {
+        program_id: "JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB".to_string(),
+        day: Some(DateRange::between("2023-01-01", "2023-01-31")),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = program_new_users(Some(request), &api_key).await.unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: ProgramNewUsersResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,45 +1,117 @@
-use serde::{Deserialize, Serialize};
-
+//! # Program Overlap
+//!
+//! POST `https://rest-api.hellomoon.io/v0/defi/program/overlap`
+//!
+//! Number of accounts that used both of two programs over a period, together with the number of
+//! users of each program.
+use crate::summary::common::{DateRange, Granularity};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct ProgramOverlapRequest {
+    /// The public key (address) of the first program
+    #[serde(rename = "programIdA")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub program_id_a: String,
+    /// The public key (address) of the second program
+    #[serde(rename = "programIdB")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub program_id_b: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the users are counted over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct ProgramOverlapResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<ProgramOverlap>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct ProgramOverlap {
+    /// The public key (address) of the first program
+    #[serde(rename = "programIdA")]
+    pub program_id_a: Option<String>,
+    /// The public key (address) of the second program
+    #[serde(rename = "programIdB")]
+    pub program_id_b: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of distinct users of the first program
+    #[serde(rename = "usersA")]
+    pub users_a: Option<u64>,
+    /// Number of distinct users of the second program
+    #[serde(rename = "usersB")]
+    pub users_b: Option<u64>,
+    /// Number of distinct accounts that used both programs
+    #[serde(rename = "overlappingUsers")]
+    pub overlapping_users: Option<u64>,
+}
+
+impl ProgramOverlap {
+    /// Share of the users of either program that used both, between 0 and 1.
+    pub fn jaccard_index(&self) -> Option<f64> {
+        let overlapping = self.overlapping_users?;
+        let union = (self.users_a? + self.users_b?).checked_sub(overlapping)?;
+        (union > 0).then(|| overlapping as f64 / union as f64)
+    }
+}
+
+pub async fn program_overlap(
+    request: Option<ProgramOverlapRequest>,
+    api_key: &str,
+) -> anyhow::Result<ProgramOverlapResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/defi/program/overlap");
+    core_call::<ProgramOverlapRequest, ProgramOverlapResponse>(request, api_url, api_key).await
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+#[test]
+fn test_program_overlap_jaccard_index() {
+    let overlap = ProgramOverlap {
+        users_a: Some(30),
+        users_b: Some(20),
+        overlapping_users: Some(10),
+        ..Default::default()
+    };
+    assert_eq!(overlap.jaccard_index(), Some(0.25));
+    assert_eq!(ProgramOverlap::default().jaccard_index(), None);
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_program_overlap() {
+    let request = ProgramOverlapRequest {
+        program_id_a: "JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB".to_string(),
+        program_id_b: "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string(),
+        day: Some(DateRange::since("2023-01-01")),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = program_overlap(Some(request), &api_key).await.unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: ProgramOverlapResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,45 +1,111 @@
-use serde::{Deserialize, Serialize};
-
+//! # Program Stats
+//!
+//! POST `https://rest-api.hellomoon.io/v0/defi/program/stats`
+//!
+//! Activity of a program over time: the number of distinct users, the number of transactions
+//! and the fees paid by those transactions, per day, week or month.
+use crate::summary::common::{DateRange, Granularity};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct ProgramStatsRequest {
+    /// The public key (address) of the account containing the program on chain.
+    /// > You can also visit https://www.hellomoon.io/id?search=program to search for a program using a user interface.
+    #[serde(rename = "programId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub program_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the stats are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct ProgramStatsResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<ProgramStats>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct ProgramStats {
+    /// The public key (address) of the account containing the program on chain.
+    #[serde(rename = "programId")]
+    pub program_id: Option<String>,
+    /// Name of the program
+    #[serde(rename = "programName")]
+    pub program_name: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of distinct accounts that signed a transaction calling the program
+    #[serde(rename = "activeUsers")]
+    pub active_users: Option<u64>,
+    /// Number of transactions calling the program
+    #[serde(rename = "transactionCount")]
+    pub transaction_count: Option<u64>,
+    /// Fees paid by the transactions calling the program, in lamports
+    #[serde(rename = "totalFees")]
+    pub total_fees: Option<u64>,
+}
+
+pub async fn program_stats(
+    request: Option<ProgramStatsRequest>,
+    api_key: &str,
+) -> anyhow::Result<ProgramStatsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/defi/program/stats");
+    core_call::<ProgramStatsRequest, ProgramStatsResponse>(request, api_url, api_key).await
+}
+
+#[test]
+fn test_program_stats_request() {
+    let request = ProgramStatsRequest {
+        program_id: "JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB".to_string(),
+        day: Some(DateRange::between("2023-01-01", "2023-01-31")),
+        granularity: Some(Granularity::OneWeek),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "programId": "JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB",
+            "day": { "operator": "between", "greaterThan": "2023-01-01", "lessThan": "2023-01-31" },
+            "granularity": "ONE_WEEK"
+        })
+    );
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_program_stats() {
+    let request = ProgramStatsRequest {
+        program_id: "JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB".to_string(),
+        day: Some(DateRange::since("2023-01-01")),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = program_stats(Some(request), &api_key).await.unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: ProgramStatsResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
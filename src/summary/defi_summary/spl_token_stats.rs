@@ -1,45 +1,147 @@
-use serde::{Deserialize, Serialize};
-
+//! # Token Stats
+//!
+//! POST `https://rest-api.hellomoon.io/v0/token/stats`
+//!
+//! Adoption and trading activity of a token per day, week or month: holders, active users,
+//! new users and the volume transferred.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct SplTokenStatsRequest {
+    /// Mint address of the token per the SPL token program
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub mint: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the stats are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SplTokenStatsResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<SplTokenStats>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SplTokenStats {
+    /// Mint address of the token per the SPL token program
+    pub mint: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of accounts holding the token at the end of the period
+    pub holders: Option<u64>,
+    /// Number of distinct accounts that sent or received the token
+    #[serde(rename = "activeUsers")]
+    pub active_users: Option<u64>,
+    /// Number of accounts that held the token for the first time
+    #[serde(rename = "newUsers")]
+    pub new_users: Option<u64>,
+    /// Amount of the token transferred, unconverted for decimals
+    pub volume: Option<u64>,
+    /// Amount of the token transferred, in USD
+    #[serde(rename = "volumeUsd")]
+    pub volume_usd: Option<f64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+pub async fn spl_token_stats(
+    request: Option<SplTokenStatsRequest>,
+    api_key: &str,
+) -> anyhow::Result<SplTokenStatsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/token/stats");
+    core_call::<SplTokenStatsRequest, SplTokenStatsResponse>(request, api_url, api_key).await
+}
+
+/// Collect stats rows into a series, rows without a day are dropped.
+pub fn spl_token_stats_series(
+    granularity: Granularity,
+    rows: impl IntoIterator<Item = SplTokenStats>,
+) -> TimeSeries<SplTokenStats> {
+    TimeSeries::from_rows(granularity, rows, |row| Some((row.day.clone()?, row)))
+}
+
+/// Every stats row of `mint` matching `day`, as a series.
+pub async fn spl_token_stats_time_series(
+    api_key: &str,
+    mint: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<SplTokenStats>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = SplTokenStatsRequest {
+            mint: mint.to_string(),
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = spl_token_stats(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(spl_token_stats_series(granularity, rows))
+}
+
+#[test]
+fn test_spl_token_stats_series() {
+    let response: SplTokenStatsResponse = serde_json::from_str(
+        r#"{
+            "data": [
+                { "mint": "m", "day": "2023-01-02", "holders": 12, "activeUsers": 5, "newUsers": 2, "volume": 900, "volumeUsd": 9.0 },
+                { "mint": "m", "day": "2023-01-01", "holders": 10, "activeUsers": 4, "newUsers": 10, "volume": 100, "volumeUsd": 1.0 },
+                { "mint": "m", "holders": 11 }
+            ],
+            "paginationToken": null
+        }"#,
+    )
+    .unwrap();
+
+    let series = spl_token_stats_series(Granularity::OneDay, response.data.unwrap_or_default());
+    let holders = series.map(|row| row.holders.unwrap_or_default());
+
+    assert_eq!(
+        holders.iter().collect::<Vec<_>>(),
+        vec![("2023-01-01", &10), ("2023-01-02", &12)]
+    );
+    assert_eq!(series.latest().unwrap().value.volume_usd, Some(9.0));
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_spl_token_stats() {
+    let request = SplTokenStatsRequest {
+        mint: "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263".to_string(),
+        day: Some(DateRange::since("2023-01-01")),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = spl_token_stats(Some(request), &api_key).await.unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: SplTokenStatsResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,45 +1,100 @@
-use serde::{Deserialize, Serialize};
-
+//! # Token Swap Pairs Weekly
+//!
+//! POST `https://rest-api.hellomoon.io/v0/jupiter/swapping-pairs-weekly`
+//!
+//! The token pairs swapped through Jupiter per week, with their volume, swaps and users.
+use crate::summary::common::DateRange;
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct SwappingPairsWeeklyRequest {
+    /// Mint of the token sent to Jupiter
+    #[serde(rename = "sourceMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub source_mint: String,
+    /// Mint of the token received from Jupiter
+    #[serde(rename = "destinationMint")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub destination_mint: String,
+    /// Only the weeks starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub week: Option<DateRange>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SwappingPairsWeeklyResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<SwappingPairWeekly>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SwappingPairWeekly {
+    /// First day of the week, `YYYY-MM-DD`
+    pub week: Option<String>,
+    /// Mint of the token sent to Jupiter
+    #[serde(rename = "sourceMint")]
+    pub source_mint: Option<String>,
+    /// Symbol of the token sent per our spl_token_list metadata
+    #[serde(rename = "sourceSymbol")]
+    pub source_symbol: Option<String>,
+    /// Mint of the token received from Jupiter
+    #[serde(rename = "destinationMint")]
+    pub destination_mint: Option<String>,
+    /// Symbol of the token received per our spl_token_list metadata
+    #[serde(rename = "destinationSymbol")]
+    pub destination_symbol: Option<String>,
+    /// Volume swapped in the week, in USD
+    #[serde(rename = "volumeUsd")]
+    pub volume_usd: Option<f64>,
+    /// Number of swaps in the week
+    #[serde(rename = "swapCount")]
+    pub swap_count: Option<u64>,
+    /// Number of distinct accounts that swapped the pair in the week
+    #[serde(rename = "uniqueUsers")]
+    pub unique_users: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+pub async fn swapping_pairs_weekly(
+    request: Option<SwappingPairsWeeklyRequest>,
+    api_key: &str,
+) -> anyhow::Result<SwappingPairsWeeklyResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/jupiter/swapping-pairs-weekly");
+    core_call::<SwappingPairsWeeklyRequest, SwappingPairsWeeklyResponse>(request, api_url, api_key)
+        .await
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_swapping_pairs_weekly() {
+    let request = SwappingPairsWeeklyRequest {
+        week: Some(DateRange::since("2023-01-01")),
+        limit: 20,
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = swapping_pairs_weekly(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: SwappingPairsWeeklyResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,45 +1,105 @@
-use serde::{Deserialize, Serialize};
-
+//! # Token Users Over Time
+//!
+//! POST `https://rest-api.hellomoon.io/v0/token/users-over-time`
+//!
+//! Number of distinct accounts that sent or received a token, per day, week or month.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct TokenUsersOverTimeRequest {
+    /// Mint address of the token per the SPL token program
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub mint: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the users are counted over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct TokenUsersOverTimeResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<TokenUsersOverTime>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct TokenUsersOverTime {
+    /// Mint address of the token per the SPL token program
+    pub mint: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of distinct accounts that sent or received the token
+    #[serde(rename = "activeUsers")]
+    pub active_users: Option<u64>,
 }
 
-#[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+pub async fn token_users_over_time(
+    request: Option<TokenUsersOverTimeRequest>,
+    api_key: &str,
+) -> anyhow::Result<TokenUsersOverTimeResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/token/users-over-time");
+    core_call::<TokenUsersOverTimeRequest, TokenUsersOverTimeResponse>(request, api_url, api_key)
+        .await
+}
 
+/// Active users of `mint` per period matching `day`.
+pub async fn token_users_time_series(
+    api_key: &str,
+    mint: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<u64>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = TokenUsersOverTimeRequest {
+            mint: mint.to_string(),
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = token_users_over_time(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(TimeSeries::from_rows(granularity, rows, |row| {
+        Some((row.day?, row.active_users.unwrap_or_default()))
+    }))
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_token_users_time_series() {
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let series = token_users_time_series(
+        &api_key,
+        "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263",
+        Granularity::OneWeek,
+        Some(DateRange::since("2023-01-01")),
+    )
+    .await
+    .unwrap();
 
-    let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
-    println!("{:#?}", right);
-    assert_eq!(left, right);
+    println!("{:#?}", series);
 }
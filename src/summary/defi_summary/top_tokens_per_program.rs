@@ -1,45 +1,95 @@
-use serde::{Deserialize, Serialize};
-
+//! # Program Top Tokens
+//!
+//! POST `https://rest-api.hellomoon.io/v0/defi/program/top-tokens`
+//!
+//! The tokens traded the most through a program over a period, ranked by volume in USD.
+use crate::summary::common::{DateRange, Granularity};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct TopTokensPerProgramRequest {
+    /// The public key (address) of the account containing the program on chain.
+    /// > You can also visit https://www.hellomoon.io/id?search=program to search for a program using a user interface.
+    #[serde(rename = "programId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub program_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the tokens are ranked over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct TopTokensPerProgramResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<TopTokenPerProgram>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct TopTokenPerProgram {
+    /// The public key (address) of the account containing the program on chain.
+    #[serde(rename = "programId")]
+    pub program_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Mint address of the token per the SPL token program
+    pub mint: Option<String>,
+    /// Symbol of the token per our spl_token_list metadata
+    pub symbol: Option<String>,
+    /// Volume of the token traded through the program, in USD
+    #[serde(rename = "volumeUsd")]
+    pub volume_usd: Option<f64>,
+    /// Number of transactions of the program moving the token
+    #[serde(rename = "transactionCount")]
+    pub transaction_count: Option<u64>,
+    /// Rank of the token by volume within the program and period, 1 is the most traded
+    pub rank: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+pub async fn top_tokens_per_program(
+    request: Option<TopTokensPerProgramRequest>,
+    api_key: &str,
+) -> anyhow::Result<TopTokensPerProgramResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/defi/program/top-tokens");
+    core_call::<TopTokensPerProgramRequest, TopTokensPerProgramResponse>(request, api_url, api_key)
+        .await
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_top_tokens_per_program() {
+    let request = TopTokensPerProgramRequest {
+        program_id: "JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB".to_string(),
+        day: Some(DateRange::on("2023-01-15")),
+        limit: 10,
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = top_tokens_per_program(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: TopTokensPerProgramResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,2 +1,3 @@
+pub mod common;
 pub mod defi_summary;
 pub mod nft_summary;
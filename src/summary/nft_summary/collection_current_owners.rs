@@ -1,45 +1,88 @@
-use serde::{Deserialize, Serialize};
-
+//! # Collection Current Owners
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/collection/current-owners`
+//!
+//! Number of distinct accounts currently holding at least one NFT of a collection.
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct CollectionCurrentOwnersRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CollectionCurrentOwnersResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<CollectionCurrentOwners>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionCurrentOwners {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// Number of distinct accounts holding at least one NFT of the collection
+    #[serde(rename = "currentOwnerCount")]
+    pub current_owner_count: Option<u64>,
+    /// Number of NFTs in the collection
+    pub supply: Option<u64>,
+}
+
+impl CollectionCurrentOwners {
+    /// Owners per NFT, between 0 and 1. 1 means every NFT is held by a different account.
+    pub fn owner_ratio(&self) -> Option<f64> {
+        let supply = self.supply.filter(|supply| *supply > 0)?;
+        Some(self.current_owner_count? as f64 / supply as f64)
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+pub async fn collection_current_owners(
+    request: Option<CollectionCurrentOwnersRequest>,
+    api_key: &str,
+) -> anyhow::Result<CollectionCurrentOwnersResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/nft/collection/current-owners");
+    core_call::<CollectionCurrentOwnersRequest, CollectionCurrentOwnersResponse>(
+        request, api_url, api_key,
+    )
+    .await
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_collection_current_owners() {
+    let request = CollectionCurrentOwnersRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = collection_current_owners(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: CollectionCurrentOwnersResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
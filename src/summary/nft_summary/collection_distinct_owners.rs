@@ -1,45 +1,119 @@
-use serde::{Deserialize, Serialize};
-
+//! # Collection Distinct Owners Over Time
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/collection/distinct-owners`
+//!
+//! Number of distinct accounts holding at least one NFT of a collection at the end of each day,
+//! week or month.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct CollectionDistinctOwnersRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the owners are counted at the end of, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CollectionDistinctOwnersResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<CollectionDistinctOwners>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionDistinctOwners {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of distinct accounts holding the collection at the end of the period
+    #[serde(rename = "distinctOwners")]
+    pub distinct_owners: Option<u64>,
+}
+
+pub async fn collection_distinct_owners(
+    request: Option<CollectionDistinctOwnersRequest>,
+    api_key: &str,
+) -> anyhow::Result<CollectionDistinctOwnersResponse> {
+    let api_url = format!(
+        "{}{}",
+        HELLOMOON_ROOT_URL, "/nft/collection/distinct-owners"
+    );
+    core_call::<CollectionDistinctOwnersRequest, CollectionDistinctOwnersResponse>(
+        request, api_url, api_key,
+    )
+    .await
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+/// Distinct owners of a collection per period matching `day`.
+pub async fn collection_distinct_owners_time_series(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<u64>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = CollectionDistinctOwnersRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = collection_distinct_owners(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(TimeSeries::from_rows(granularity, rows, |row| {
+        Some((row.day?, row.distinct_owners.unwrap_or_default()))
+    }))
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_collection_distinct_owners() {
+    let request = CollectionDistinctOwnersRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        day: Some(DateRange::since("2023-01-01")),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = collection_distinct_owners(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: CollectionDistinctOwnersResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,46 +1,151 @@
-use serde::{Deserialize, Serialize};
-
+//! # NFT Collection Listing Stats
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/collection/listing-stats`
+//!
+//! Number and share of the NFTs of a collection listed on a marketplace, per day, week or month.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct CollectionListingStatsRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the listings are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CollectionListingStatsResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<CollectionListingStats>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionListingStats {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of NFTs listed at the end of the period
+    #[serde(rename = "listedCount")]
+    pub listed_count: Option<u64>,
+    /// Number of NFTs in the collection
+    pub supply: Option<u64>,
+    /// Share of the supply listed at the end of the period, in percent
+    #[serde(rename = "listedPercentage")]
+    pub listed_percentage: Option<f64>,
+    /// Number of listings created in the period
+    #[serde(rename = "newListings")]
+    pub new_listings: Option<u64>,
+    /// Number of listings cancelled in the period
+    pub delistings: Option<u64>,
+}
+
+impl CollectionListingStats {
+    /// The listed percentage, computed from the listed count and supply when missing.
+    pub fn listed_percentage(&self) -> Option<f64> {
+        self.listed_percentage.or_else(|| {
+            let supply = self.supply.filter(|supply| *supply > 0)?;
+            Some(self.listed_count? as f64 * 100.0 / supply as f64)
+        })
+    }
+}
+
+pub async fn collection_listing_stats(
+    request: Option<CollectionListingStatsRequest>,
+    api_key: &str,
+) -> anyhow::Result<CollectionListingStatsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/nft/collection/listing-stats");
+    core_call::<CollectionListingStatsRequest, CollectionListingStatsResponse>(
+        request, api_url, api_key,
+    )
+    .await
+}
+
+/// Every listing stats row of a collection matching `day`, as a series.
+pub async fn collection_listing_stats_time_series(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<CollectionListingStats>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = CollectionListingStatsRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = collection_listing_stats(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(TimeSeries::from_rows(granularity, rows, |row| {
+        Some((row.day.clone()?, row))
+    }))
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
+#[test]
+fn test_collection_listed_percentage() {
+    let stats: CollectionListingStats = serde_json::from_str(
+        r#"{ "helloMoonCollectionId": "c", "day": "2023-01-01", "listedCount": 500, "supply": 10000 }"#,
+    )
+    .unwrap();
+    assert_eq!(stats.listed_percentage(), Some(5.0));
 
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+    let reported = CollectionListingStats {
+        listed_percentage: Some(4.2),
+        ..stats
+    };
+    assert_eq!(reported.listed_percentage(), Some(4.2));
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_collection_listing_stats() {
+    let request = CollectionListingStatsRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        day: Some(DateRange::since("2023-01-01")),
+        granularity: Some(Granularity::OneWeek),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = collection_listing_stats(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: CollectionListingStatsResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,46 +1,149 @@
-use serde::{Deserialize, Serialize};
-
+//! # Collection Mint Stats
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/collection/mint-stats`
+//!
+//! Mint progress of a collection per day, week or month: the mints of the period, the total
+//! minted so far against the supply, and the primary sale volume. Prices are in lamports.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct CollectionMintStatsRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the mints are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CollectionMintStatsResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<CollectionMintStats>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionMintStats {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of NFTs minted in the period
+    #[serde(rename = "mintCount")]
+    pub mint_count: Option<u64>,
+    /// Number of NFTs minted since the collection launched, up to the end of the period
+    #[serde(rename = "totalMinted")]
+    pub total_minted: Option<u64>,
+    /// Number of NFTs the collection will have once fully minted
+    pub supply: Option<u64>,
+    /// Number of distinct accounts that minted in the period
+    #[serde(rename = "uniqueMinters")]
+    pub unique_minters: Option<u64>,
+    /// Sum of the mint prices paid in the period, in lamports
+    #[serde(rename = "mintVolume")]
+    pub mint_volume: Option<u64>,
+}
+
+impl CollectionMintStats {
+    /// Share of the supply minted at the end of the period, between 0 and 1.
+    pub fn mint_progress(&self) -> Option<f64> {
+        let supply = self.supply.filter(|supply| *supply > 0)?;
+        Some(self.total_minted? as f64 / supply as f64)
+    }
+}
+
+pub async fn collection_mint_stats(
+    request: Option<CollectionMintStatsRequest>,
+    api_key: &str,
+) -> anyhow::Result<CollectionMintStatsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/nft/collection/mint-stats");
+    core_call::<CollectionMintStatsRequest, CollectionMintStatsResponse>(request, api_url, api_key)
+        .await
+}
+
+/// Every mint stats row of a collection matching `day`, as a series.
+pub async fn collection_mint_stats_time_series(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<CollectionMintStats>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = CollectionMintStatsRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = collection_mint_stats(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(TimeSeries::from_rows(granularity, rows, |row| {
+        Some((row.day.clone()?, row))
+    }))
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
+#[test]
+fn test_collection_mint_progress() {
+    let stats = CollectionMintStats {
+        total_minted: Some(2500),
+        supply: Some(10_000),
+        ..Default::default()
+    };
+    assert_eq!(stats.mint_progress(), Some(0.25));
 
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+    let unknown_supply = CollectionMintStats {
+        total_minted: Some(2500),
+        supply: Some(0),
+        ..Default::default()
+    };
+    assert_eq!(unknown_supply.mint_progress(), None);
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_collection_mint_stats() {
+    let request = CollectionMintStatsRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = collection_mint_stats(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: CollectionMintStatsResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,46 +1,257 @@
-use serde::{Deserialize, Serialize};
-
+//! # Collection Overlap
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/collection/overlap`
+//!
+//! Number of accounts currently holding NFTs of both of two collections, together with the
+//! number of holders of each collection. Listed as Collection Ownership Overlap in the DeFi
+//! summary as well.
+//!
+//! [`collection_overlap_matrix`] queries every pair of a list of collections and collects the
+//! results into an [`OverlapMatrix`].
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct CollectionOverlapRequest {
+    /// helloMoonCollectionId of the first collection
+    #[serde(rename = "helloMoonCollectionIdA")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id_a: String,
+    /// helloMoonCollectionId of the second collection
+    #[serde(rename = "helloMoonCollectionIdB")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id_b: String,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CollectionOverlapResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<CollectionOverlap>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionOverlap {
+    /// helloMoonCollectionId of the first collection
+    #[serde(rename = "helloMoonCollectionIdA")]
+    pub hello_moon_collection_id_a: Option<String>,
+    /// helloMoonCollectionId of the second collection
+    #[serde(rename = "helloMoonCollectionIdB")]
+    pub hello_moon_collection_id_b: Option<String>,
+    /// Number of distinct holders of the first collection
+    #[serde(rename = "holdersA")]
+    pub holders_a: Option<u64>,
+    /// Number of distinct holders of the second collection
+    #[serde(rename = "holdersB")]
+    pub holders_b: Option<u64>,
+    /// Number of distinct accounts holding both collections
+    #[serde(rename = "sharedHolders")]
+    pub shared_holders: Option<u64>,
+}
+
+impl CollectionOverlap {
+    /// Share of the holders of either collection that hold both, between 0 and 1.
+    pub fn jaccard_index(&self) -> Option<f64> {
+        jaccard_index(self.holders_a?, self.holders_b?, self.shared_holders?)
+    }
+
+    /// Share of the holders of the first collection that also hold the second, between 0 and 1.
+    pub fn overlap_ratio(&self) -> Option<f64> {
+        let holders_a = self.holders_a.filter(|holders| *holders > 0)?;
+        Some(self.shared_holders? as f64 / holders_a as f64)
+    }
+}
+
+fn jaccard_index(holders_a: u64, holders_b: u64, shared_holders: u64) -> Option<f64> {
+    let union = (holders_a + holders_b).checked_sub(shared_holders)?;
+    (union > 0).then(|| shared_holders as f64 / union as f64)
+}
+
+pub async fn collection_overlap(
+    request: Option<CollectionOverlapRequest>,
+    api_key: &str,
+) -> anyhow::Result<CollectionOverlapResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/nft/collection/overlap");
+    core_call::<CollectionOverlapRequest, CollectionOverlapResponse>(request, api_url, api_key)
+        .await
+}
+
+/// Shared holders between every pair of a list of collections.
+///
+/// Cells are `None` for pairs without data and on the diagonal, the number of holders of each
+/// collection is kept apart in `holders`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct OverlapMatrix {
+    /// helloMoonCollectionId of each row and column
+    pub collections: Vec<String>,
+    /// Number of holders of each collection, in the order of `collections`
+    pub holders: Vec<Option<u64>>,
+    pub shared_holders: Vec<Vec<Option<u64>>>,
+}
+
+impl OverlapMatrix {
+    /// Fill the matrix of `collections` from overlap rows, in either order of the pair.
+    /// Rows about other collections are ignored.
+    pub fn from_overlaps(collections: Vec<String>, overlaps: &[CollectionOverlap]) -> Self {
+        let n = collections.len();
+        let mut holders = vec![None; n];
+        let mut shared_holders = vec![vec![None; n]; n];
+        let index = |id: &Option<String>| {
+            let id = id.as_deref()?;
+            collections.iter().position(|collection| collection == id)
+        };
+        for overlap in overlaps {
+            let (Some(a), Some(b)) = (
+                index(&overlap.hello_moon_collection_id_a),
+                index(&overlap.hello_moon_collection_id_b),
+            ) else {
+                continue;
+            };
+            if a != b {
+                shared_holders[a][b] = overlap.shared_holders;
+                shared_holders[b][a] = overlap.shared_holders;
+            }
+            if overlap.holders_a.is_some() {
+                holders[a] = overlap.holders_a;
+            }
+            if overlap.holders_b.is_some() {
+                holders[b] = overlap.holders_b;
+            }
+        }
+        Self {
+            collections,
+            holders,
+            shared_holders,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.collections.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.collections.is_empty()
+    }
+
+    fn index(&self, hello_moon_collection_id: &str) -> Option<usize> {
+        self.collections
+            .iter()
+            .position(|collection| collection == hello_moon_collection_id)
+    }
+
+    /// Number of accounts holding both collections, `None` when `a == b`.
+    pub fn shared_holders(&self, a: &str, b: &str) -> Option<u64> {
+        self.shared_holders[self.index(a)?][self.index(b)?]
+    }
+
+    /// Number of holders of a collection.
+    pub fn holders(&self, hello_moon_collection_id: &str) -> Option<u64> {
+        self.holders[self.index(hello_moon_collection_id)?]
+    }
+
+    /// Share of the holders of either collection that hold both, between 0 and 1.
+    pub fn jaccard_index(&self, a: &str, b: &str) -> Option<f64> {
+        jaccard_index(
+            self.holders(a)?,
+            self.holders(b)?,
+            self.shared_holders(a, b)?,
+        )
+    }
+
+    /// Share of the holders of `a` that also hold `b`, between 0 and 1.
+    pub fn overlap_ratio(&self, a: &str, b: &str) -> Option<f64> {
+        let holders_a = self.holders(a).filter(|holders| *holders > 0)?;
+        Some(self.shared_holders(a, b)? as f64 / holders_a as f64)
+    }
+}
+
+/// Query the overlap of every pair of `collections`, one request per pair.
+pub async fn collection_overlap_matrix(
+    api_key: &str,
+    collections: &[String],
+) -> anyhow::Result<OverlapMatrix> {
+    let mut overlaps = Vec::new();
+    for (i, a) in collections.iter().enumerate() {
+        for b in &collections[i + 1..] {
+            let request = CollectionOverlapRequest {
+                hello_moon_collection_id_a: a.clone(),
+                hello_moon_collection_id_b: b.clone(),
+                ..Default::default()
+            };
+            let response = collection_overlap(Some(request), api_key).await?;
+            overlaps.extend(response.data.unwrap_or_default());
+        }
+    }
+    Ok(OverlapMatrix::from_overlaps(
+        collections.to_vec(),
+        &overlaps,
+    ))
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
+#[test]
+fn test_overlap_matrix() {
+    let (a, b, c, z) = (
+        "040de757c0d2b75dcee999ddd47689c4",
+        "fd3eb1ab3d0ab54ad4a8e2cf8ea3e2b5",
+        "3e7c5b3e5d8a4b0f9c2a1d6e8f7b4c19",
+        "a1b7e2c94f0d3e6b8c5a2f1d7e9b4c03",
+    );
+    let response: CollectionOverlapResponse =
+        crate::fixture("collection_overlap/collection_overlap");
+    let overlaps = response.data.unwrap();
+    let matrix =
+        OverlapMatrix::from_overlaps(vec![a.to_string(), b.to_string(), c.to_string()], &overlaps);
 
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+    assert_eq!(matrix.len(), 3);
+    assert_eq!(matrix.holders(a), Some(100));
+    assert_eq!(matrix.holders, vec![Some(100), Some(50), Some(40)]);
+    assert_eq!(matrix.shared_holders(a, a), None);
+    assert_eq!(
+        matrix
+            .shared_holders
+            .iter()
+            .flatten()
+            .flatten()
+            .sum::<u64>(),
+        (30 + 10) * 2
+    );
+    assert_eq!(matrix.shared_holders(b, a), Some(30));
+    assert_eq!(matrix.shared_holders(a, c), Some(10));
+    assert_eq!(matrix.shared_holders(b, c), None);
+    assert_eq!(matrix.shared_holders(a, z), None);
+    assert_eq!(matrix.jaccard_index(a, b), Some(0.25));
+    assert_eq!(matrix.overlap_ratio(b, a), Some(0.6));
+    assert_eq!(overlaps[0].overlap_ratio(), Some(0.3));
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_collection_overlap() {
+    let request = CollectionOverlapRequest {
+        hello_moon_collection_id_a: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        hello_moon_collection_id_b: "fd3eb1ab3d0ab54ad4a8e2cf8ea3e2b5".to_string(),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = collection_overlap(Some(request), &api_key).await.unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: CollectionOverlapResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
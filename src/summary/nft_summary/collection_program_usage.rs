@@ -1,45 +1,149 @@
-use serde::{Deserialize, Serialize};
-
+//! # Collection Program Usage
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/collection/program-usage`
+//!
+//! Activity on the NFTs of a collection per program (marketplaces, lending protocols, staking
+//! programs...) for each day, week or month.
+use crate::summary::common::{DateRange, Granularity};
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct CollectionProgramUsageRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The public key (address) of a program
+    #[serde(rename = "programId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub program_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the activity is aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CollectionProgramUsageResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<CollectionProgramUsage>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionProgramUsage {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// The public key (address) of the program
+    #[serde(rename = "programId")]
+    pub program_id: Option<String>,
+    /// Name of the program, e.g. a marketplace
+    #[serde(rename = "programName")]
+    pub program_name: Option<String>,
+    /// Number of transactions involving an NFT of the collection
+    #[serde(rename = "transactionCount")]
+    pub transaction_count: Option<u64>,
+    /// Number of distinct accounts that signed these transactions
+    #[serde(rename = "distinctUsers")]
+    pub distinct_users: Option<u64>,
+    /// Number of distinct NFTs of the collection involved
+    #[serde(rename = "distinctNfts")]
+    pub distinct_nfts: Option<u64>,
+}
+
+/// Share of the transactions of each program over all `rows`, between 0 and 1, largest first.
+///
+/// Programs are keyed by name, or by program id when the name is missing.
+pub fn program_usage_shares(rows: &[CollectionProgramUsage]) -> Vec<(String, f64)> {
+    let mut transactions: BTreeMap<String, u64> = BTreeMap::new();
+    for row in rows {
+        let Some(program) = row.program_name.clone().or_else(|| row.program_id.clone()) else {
+            continue;
+        };
+        *transactions.entry(program).or_default() += row.transaction_count.unwrap_or_default();
+    }
+    let total: u64 = transactions.values().sum();
+    if total == 0 {
+        return Vec::new();
+    }
+    let mut shares: Vec<(String, f64)> = transactions
+        .into_iter()
+        .map(|(program, count)| (program, count as f64 / total as f64))
+        .collect();
+    shares.sort_by(|a, b| b.1.total_cmp(&a.1));
+    shares
+}
+
+pub async fn collection_program_usage(
+    request: Option<CollectionProgramUsageRequest>,
+    api_key: &str,
+) -> anyhow::Result<CollectionProgramUsageResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/nft/collection/program-usage");
+    core_call::<CollectionProgramUsageRequest, CollectionProgramUsageResponse>(
+        request, api_url, api_key,
+    )
+    .await
+}
+
+#[test]
+fn test_program_usage_shares() {
+    let response: CollectionProgramUsageResponse =
+        crate::fixture("collection_program_usage/collection_program_usage");
+    let rows = response.data.unwrap();
+
+    let shares = program_usage_shares(&rows);
+    assert_eq!(shares[0], ("Magic Eden v2".to_string(), 0.6));
+    assert_eq!(
+        shares[1],
+        (
+            "SHARKobtfF1bHhxD2eqftjHBdVSCbKo9JtgK71FhELP".to_string(),
+            0.3
+        )
+    );
+    assert_eq!(shares[2], ("Tensor".to_string(), 0.1));
+    assert!(program_usage_shares(&[]).is_empty());
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_collection_program_usage() {
+    let request = CollectionProgramUsageRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        day: Some(DateRange::since("2023-01-01")),
+        granularity: Some(Granularity::OneWeek),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = collection_program_usage(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: CollectionProgramUsageResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,45 +1,138 @@
+//! # Collection Stats With Floor Price
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/collection/stats`
+//!
+//! Trading activity of a collection per day, week or month: floor price, volume, sales and
+//! the number of distinct buyers and sellers. Prices are in lamports.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
 use serde::{Deserialize, Serialize};
 
-use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
-
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct CollectionStatsRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the stats are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CollectionStatsResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<CollectionStats>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionStats {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// Name of the collection
+    #[serde(rename = "collectionName")]
+    pub collection_name: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// The lowest listing price at the end of the period, in lamports
+    #[serde(rename = "floorPrice")]
+    pub floor_price: Option<u64>,
+    /// Sum of the prices of the sales of the period, in lamports
+    pub volume: Option<u64>,
+    /// Number of secondary sales in the period
+    #[serde(rename = "salesCount")]
+    pub sales_count: Option<u64>,
+    /// Average price of the sales of the period, in lamports
+    #[serde(rename = "averagePrice")]
+    pub average_price: Option<u64>,
+    /// Number of distinct buyers in the period
+    #[serde(rename = "uniqueBuyers")]
+    pub unique_buyers: Option<u64>,
+    /// Number of distinct sellers in the period
+    #[serde(rename = "uniqueSellers")]
+    pub unique_sellers: Option<u64>,
+}
+
+impl CollectionStats {
+    pub fn floor_price_sol(&self) -> Option<f64> {
+        self.floor_price.map(lamports_to_sol)
+    }
+
+    pub fn volume_sol(&self) -> Option<f64> {
+        self.volume.map(lamports_to_sol)
+    }
+}
+
+pub async fn collection_stats(
+    request: Option<CollectionStatsRequest>,
+    api_key: &str,
+) -> anyhow::Result<CollectionStatsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/nft/collection/stats");
+    core_call::<CollectionStatsRequest, CollectionStatsResponse>(request, api_url, api_key).await
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+/// Every stats row of a collection matching `day`, as a series.
+pub async fn collection_stats_time_series(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<CollectionStats>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = CollectionStatsRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = collection_stats(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(TimeSeries::from_rows(granularity, rows, |row| {
+        Some((row.day.clone()?, row))
+    }))
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_collection_stats() {
+    let request = CollectionStatsRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        day: Some(DateRange::since("2023-01-01")),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = collection_stats(Some(request), &api_key).await.unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: CollectionStatsResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,46 +1,118 @@
-use serde::{Deserialize, Serialize};
-
+//! # Collection Top Holders
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/collection/top-holders`
+//!
+//! The accounts holding the most NFTs of a collection, ranked by number of NFTs held.
+use crate::HELLOMOON_ROOT_URL;
 use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct CollectionTopHolderRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CollectionTopHolderResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<CollectionTopHolder>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionTopHolder {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// PublicKey of the holder
+    #[serde(rename = "ownerAccount")]
+    pub owner_account: Option<String>,
+    /// Number of NFTs of the collection held by the account
+    #[serde(rename = "nftCount")]
+    pub nft_count: Option<u64>,
+    /// Share of the supply held by the account, in percent
+    #[serde(rename = "supplyPercentage")]
+    pub supply_percentage: Option<f64>,
+    /// Rank of the holder, 1 holds the most NFTs
+    pub rank: Option<u32>,
+}
+
+/// Share of `supply` held by the `top` largest holders, between 0 and 1.
+///
+/// `holders` may come in any order, `None` when the supply is zero.
+pub fn holder_concentration(
+    holders: &[CollectionTopHolder],
+    top: usize,
+    supply: u64,
+) -> Option<f64> {
+    if supply == 0 {
+        return None;
+    }
+    let mut counts: Vec<u64> = holders
+        .iter()
+        .map(|holder| holder.nft_count.unwrap_or_default())
+        .collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    let held: u64 = counts.iter().take(top).sum();
+    Some(held as f64 / supply as f64)
+}
+
+pub async fn collection_top_holder(
+    request: Option<CollectionTopHolderRequest>,
+    api_key: &str,
+) -> anyhow::Result<CollectionTopHolderResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/nft/collection/top-holders");
+    core_call::<CollectionTopHolderRequest, CollectionTopHolderResponse>(request, api_url, api_key)
+        .await
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
+#[test]
+fn test_holder_concentration() {
+    let response: CollectionTopHolderResponse =
+        crate::fixture("collection_top_holder/collection_top_holder");
+    let mut holders = response.data.unwrap();
+    // the order of the rows does not matter
+    holders.reverse();
 
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+    assert_eq!(holder_concentration(&holders, 2, 10_000), Some(0.044));
+    assert_eq!(holder_concentration(&holders, 10, 1_000), Some(0.5));
+    assert_eq!(holder_concentration(&holders, 2, 0), None);
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_collection_top_holder() {
+    let request = CollectionTopHolderRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        limit: 10,
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = collection_top_holder(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: CollectionTopHolderResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,46 +1,143 @@
+//! # Collection Washtrading Index
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/collection/washtrading-index`
+//!
+//! Hello Moon's estimate of the share of a collection's trading that is wash trading, per day,
+//! week or month. The index goes from 0 (no wash trading) to 100. Amounts are in lamports.
+//!
+//! [`crate::analysis::wash_trading`] computes a score on the same scale from the secondary sales.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
 use serde::{Deserialize, Serialize};
 
-use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
-
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct CollectionWashtradingIndexRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the sales are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CollectionWashtradingIndexResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<CollectionWashtradingIndex>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CollectionWashtradingIndex {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Share of the trading that is wash trading, from 0 to 100
+    #[serde(rename = "washTradingIndex")]
+    pub wash_trading_index: Option<f64>,
+    /// Number of sales flagged as wash trading
+    #[serde(rename = "washTradingSalesCount")]
+    pub wash_trading_sales_count: Option<u64>,
+    /// Volume of the sales flagged as wash trading, in lamports
+    #[serde(rename = "washTradingVolume")]
+    pub wash_trading_volume: Option<u64>,
+    /// Number of sales
+    #[serde(rename = "salesCount")]
+    pub sales_count: Option<u64>,
+    /// Total price of the sales, in lamports
+    pub volume: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
+impl CollectionWashtradingIndex {
+    pub fn wash_trading_volume_sol(&self) -> Option<f64> {
+        self.wash_trading_volume.map(lamports_to_sol)
+    }
+
+    /// Volume without the wash trading, in lamports.
+    pub fn organic_volume(&self) -> Option<u64> {
+        Some(self.volume?.saturating_sub(self.wash_trading_volume?))
+    }
+}
+
+pub async fn collection_washtrading_index(
+    request: Option<CollectionWashtradingIndexRequest>,
+    api_key: &str,
+) -> anyhow::Result<CollectionWashtradingIndexResponse> {
+    let api_url = format!(
+        "{}{}",
+        HELLOMOON_ROOT_URL, "/nft/collection/washtrading-index"
+    );
+    core_call::<CollectionWashtradingIndexRequest, CollectionWashtradingIndexResponse>(
+        request, api_url, api_key,
+    )
+    .await
+}
 
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+/// Wash trading index of a collection per period matching `day`.
+pub async fn collection_washtrading_index_time_series(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<CollectionWashtradingIndex>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = CollectionWashtradingIndexRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = collection_washtrading_index(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(TimeSeries::from_rows(granularity, rows, |row| {
+        Some((row.day.clone()?, row))
+    }))
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_collection_washtrading_index() {
+    let request = CollectionWashtradingIndexRequest {
+        hello_moon_collection_id: "040de757c0d2b75dcee999ddd47689c4".to_string(),
+        day: Some(DateRange::since("2023-01-01")),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = collection_washtrading_index(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: CollectionWashtradingIndexResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -0,0 +1,147 @@
+//! # Frakt Bonds Summary
+//!
+//! POST `https://rest-api.hellomoon.io/v0/frakt/bonds/summary`
+//!
+//! Frakt bonds of a collection per day, week or month: bonds sold, repaid and liquidated, and
+//! the amount borrowed against them. Amounts are in lamports.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FraktBondsSummaryRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the bonds are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FraktBondsSummaryResponse {
+    /// array of objects
+    pub data: Option<Vec<FraktBondsSummary>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct FraktBondsSummary {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of bonds sold to an offer
+    #[serde(rename = "bondsIssued")]
+    pub bonds_issued: Option<u64>,
+    /// Number of bonds repaid
+    #[serde(rename = "bondsRepaid")]
+    pub bonds_repaid: Option<u64>,
+    /// Number of bonds liquidated
+    #[serde(rename = "bondsLiquidated")]
+    pub bonds_liquidated: Option<u64>,
+    /// Amount borrowed against the bonds issued, in lamports
+    #[serde(rename = "bondVolume")]
+    pub bond_volume: Option<u64>,
+    /// Amount paid back to lenders, interest included, in lamports
+    #[serde(rename = "repaidVolume")]
+    pub repaid_volume: Option<u64>,
+    /// Number of distinct borrowers
+    #[serde(rename = "uniqueBorrowers")]
+    pub unique_borrowers: Option<u64>,
+    /// Number of distinct lenders
+    #[serde(rename = "uniqueLenders")]
+    pub unique_lenders: Option<u64>,
+}
+
+impl FraktBondsSummary {
+    pub fn bond_volume_sol(&self) -> Option<f64> {
+        self.bond_volume.map(lamports_to_sol)
+    }
+
+    pub fn repaid_volume_sol(&self) -> Option<f64> {
+        self.repaid_volume.map(lamports_to_sol)
+    }
+
+    /// Share of the bonds closed in the period that were liquidated, between 0 and 1.
+    pub fn liquidation_rate(&self) -> Option<f64> {
+        let liquidated = self.bonds_liquidated?;
+        let closed = liquidated + self.bonds_repaid?;
+        (closed > 0).then(|| liquidated as f64 / closed as f64)
+    }
+}
+
+pub async fn frakt_bonds_summary(
+    request: Option<FraktBondsSummaryRequest>,
+    api_key: &str,
+) -> anyhow::Result<FraktBondsSummaryResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/frakt/bonds/summary");
+    core_call::<FraktBondsSummaryRequest, FraktBondsSummaryResponse>(request, api_url, api_key)
+        .await
+}
+
+/// Frakt bonds of a collection per period matching `day`.
+pub async fn frakt_bonds_summary_time_series(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<FraktBondsSummary>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = FraktBondsSummaryRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = frakt_bonds_summary(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(TimeSeries::from_rows(granularity, rows, |row| {
+        Some((row.day.clone()?, row))
+    }))
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_frakt_bonds_summary() {
+    let request = FraktBondsSummaryRequest {
+        day: Some(DateRange::since("2023-01-01")),
+        granularity: Some(Granularity::OneWeek),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = frakt_bonds_summary(Some(request), &api_key).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: FraktBondsSummaryResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
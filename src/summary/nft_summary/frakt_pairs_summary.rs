@@ -0,0 +1,165 @@
+//! # Frakt Pairs Summary
+//!
+//! POST `https://rest-api.hellomoon.io/v0/frakt/pairs/summary`
+//!
+//! Current state of the Frakt bond pairs, the offers lenders post on a market to buy bonds of a
+//! collection: liquidity left, bonds outstanding and bond volume since the pair was created.
+//! Amounts are in lamports.
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FraktPairsSummaryRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// The address of the market account
+    #[serde(rename = "hadoMarket")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hado_market: String,
+    /// The address of the pair account
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pair: String,
+    /// PublicKey of the lender who created the pair
+    #[serde(rename = "assetReceiver")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub asset_receiver: String,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct FraktPairsSummaryResponse {
+    /// array of objects
+    pub data: Option<Vec<FraktPairSummary>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct FraktPairSummary {
+    /// The address of the pair account
+    pub pair: Option<String>,
+    /// The address of the market account
+    #[serde(rename = "hadoMarket")]
+    pub hado_market: Option<String>,
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// PublicKey of the lender who created the pair
+    #[serde(rename = "assetReceiver")]
+    pub asset_receiver: Option<String>,
+    /// Amount left in the pair to buy bonds with, in lamports
+    pub liquidity: Option<u64>,
+    /// Price of the next bond token bought by the pair, in lamports
+    #[serde(rename = "currentSpotPrice")]
+    pub current_spot_price: Option<u64>,
+    /// Amount lent through bonds not repaid nor liquidated yet, in lamports
+    #[serde(rename = "outstandingVolume")]
+    pub outstanding_volume: Option<u64>,
+    /// Number of bonds bought by the pair
+    #[serde(rename = "bondsCount")]
+    pub bonds_count: Option<u64>,
+    /// Amount lent through bonds since the pair was created, in lamports
+    #[serde(rename = "bondVolume")]
+    pub bond_volume: Option<u64>,
+}
+
+impl FraktPairSummary {
+    pub fn liquidity_sol(&self) -> Option<f64> {
+        self.liquidity.map(lamports_to_sol)
+    }
+
+    pub fn bond_volume_sol(&self) -> Option<f64> {
+        self.bond_volume.map(lamports_to_sol)
+    }
+
+    /// Share of the funds of the pair lent out, between 0 and 1.
+    pub fn utilization(&self) -> Option<f64> {
+        let outstanding = self.outstanding_volume?;
+        let total = outstanding + self.liquidity?;
+        (total > 0).then(|| outstanding as f64 / total as f64)
+    }
+}
+
+/// Liquidity and bond volume of `pairs` added up per collection, in lamports.
+///
+/// Pairs without a collection are left out.
+pub fn liquidity_by_collection(pairs: &[FraktPairSummary]) -> BTreeMap<String, (u64, u64)> {
+    let mut collections: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+    for pair in pairs {
+        let Some(hello_moon_collection_id) = &pair.hello_moon_collection_id else {
+            continue;
+        };
+        let (liquidity, bond_volume) = collections
+            .entry(hello_moon_collection_id.clone())
+            .or_default();
+        *liquidity += pair.liquidity.unwrap_or_default();
+        *bond_volume += pair.bond_volume.unwrap_or_default();
+    }
+    collections
+}
+
+pub async fn frakt_pairs_summary(
+    request: Option<FraktPairsSummaryRequest>,
+    api_key: &str,
+) -> anyhow::Result<FraktPairsSummaryResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/frakt/pairs/summary");
+    core_call::<FraktPairsSummaryRequest, FraktPairsSummaryResponse>(request, api_url, api_key)
+        .await
+}
+
+#[test]
+fn test_liquidity_by_collection() {
+    let response: FraktPairsSummaryResponse =
+        crate::fixture("frakt_pairs_summary/frakt_pairs_summary");
+    let pairs = response.data.unwrap();
+
+    assert_eq!(pairs[0].utilization(), Some(0.25));
+    assert_eq!(pairs[0].liquidity_sol(), Some(3.0));
+    assert_eq!(
+        liquidity_by_collection(&pairs),
+        BTreeMap::from([
+            (
+                "040de757c0d2b75dcee999ddd47689c4".to_string(),
+                (4_000_000_000, 2_000_000_000)
+            ),
+            (
+                "fd3eb1ab3d0ab54ad4a8e2cf8ea3e2b5".to_string(),
+                (500_000_000, 0)
+            ),
+        ])
+    );
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_frakt_pairs_summary() {
+    let request = FraktPairsSummaryRequest {
+        limit: 10,
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = frakt_pairs_summary(Some(request), &api_key).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: FraktPairsSummaryResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
@@ -1,46 +1,184 @@
+//! # Marketplace Sales Over Time
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/marketplace/sales-over-time`
+//!
+//! Number of sales and volume per marketplace for each day, week or month, optionally for a
+//! single collection. Amounts are in lamports.
+use crate::dimension_facts::nft::secondary_sales::{serialize_marketplace_filter, Marketplace};
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimePoint, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
 use serde::{Deserialize, Serialize};
-
-use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use std::collections::BTreeMap;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct MarketSalesOverTimeRequest {
+    /// Only the sales of this marketplace, every marketplace by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_marketplace_filter")]
+    pub marketplace: Option<Marketplace>,
+    /// Only the sales of this collection, every collection by default
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the sales are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct MarketSalesOverTimeResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<MarketSalesOverTime>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct MarketSalesOverTime {
+    /// The marketplace the sales happened on
+    pub marketplace: Option<Marketplace>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Number of sales
+    #[serde(rename = "salesCount")]
+    pub sales_count: Option<u64>,
+    /// Total price of the sales, in lamports
+    pub volume: Option<u64>,
+}
+
+impl MarketSalesOverTime {
+    pub fn volume_sol(&self) -> Option<f64> {
+        self.volume.map(lamports_to_sol)
+    }
+}
+
+/// Split rows into one series per marketplace, rows without a marketplace or day are dropped.
+///
+/// Rows of the same marketplace and day are added up, e.g. the rows of several marketplaces
+/// unknown to this crate, which all deserialize to [`Marketplace::Other`].
+pub fn sales_per_marketplace(
+    granularity: Granularity,
+    rows: impl IntoIterator<Item = MarketSalesOverTime>,
+) -> BTreeMap<Marketplace, TimeSeries<MarketSalesOverTime>> {
+    let mut days_per_marketplace: BTreeMap<Marketplace, BTreeMap<String, MarketSalesOverTime>> =
+        BTreeMap::new();
+    for row in rows {
+        let (Some(marketplace), Some(day)) = (row.marketplace, row.day.clone()) else {
+            continue;
+        };
+        let days = days_per_marketplace.entry(marketplace).or_default();
+        let Some(total) = days.get_mut(&day) else {
+            days.insert(day, row);
+            continue;
+        };
+        let add = |a: Option<u64>, b: Option<u64>| match (a, b) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or_default() + b.unwrap_or_default()),
+        };
+        total.sales_count = add(total.sales_count, row.sales_count);
+        total.volume = add(total.volume, row.volume);
+    }
+    days_per_marketplace
+        .into_iter()
+        .map(|(marketplace, days)| {
+            let series = TimeSeries::new(
+                granularity,
+                days.into_iter()
+                    .map(|(day, value)| TimePoint { day, value }),
+            );
+            (marketplace, series)
+        })
+        .collect()
+}
+
+pub async fn market_sales_over_time(
+    request: Option<MarketSalesOverTimeRequest>,
+    api_key: &str,
+) -> anyhow::Result<MarketSalesOverTimeResponse> {
+    let api_url = format!(
+        "{}{}",
+        HELLOMOON_ROOT_URL, "/nft/marketplace/sales-over-time"
+    );
+    core_call::<MarketSalesOverTimeRequest, MarketSalesOverTimeResponse>(request, api_url, api_key)
+        .await
+}
+
+/// Sales of every marketplace per period matching `day`, one series per marketplace.
+pub async fn market_sales_time_series(
+    api_key: &str,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<BTreeMap<Marketplace, TimeSeries<MarketSalesOverTime>>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = MarketSalesOverTimeRequest {
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = market_sales_over_time(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(sales_per_marketplace(granularity, rows))
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
+#[test]
+fn test_sales_per_marketplace() {
+    // TENSOR and SOLSNIPER are both unknown to this crate, the last row has no marketplace
+    let response: MarketSalesOverTimeResponse =
+        crate::fixture("market_sales_over_time/market_sales_over_time");
+    let rows = response.data.unwrap();
+    assert_eq!(rows[4].marketplace, Some(Marketplace::Other));
 
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+    let series = sales_per_marketplace(Granularity::OneDay, rows);
+    assert_eq!(series.len(), 3);
+    let magic_eden = &series[&Marketplace::Mev2];
+    assert_eq!(magic_eden.len(), 2);
+    assert_eq!(magic_eden.points[0].day, "2023-01-01");
+    assert_eq!(magic_eden.latest().unwrap().value.volume_sol(), Some(20.0));
+    assert_eq!(series[&Marketplace::Hadeswap].len(), 1);
+    let other = series[&Marketplace::Other].get("2023-01-01").unwrap();
+    assert_eq!(other.sales_count, Some(7));
+    assert_eq!(other.volume_sol(), Some(7.0));
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_market_sales_over_time() {
+    let request = MarketSalesOverTimeRequest {
+        marketplace: Some(Marketplace::Mev2),
+        day: Some(DateRange::since("2023-01-01")),
+        granularity: Some(Granularity::OneWeek),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = market_sales_over_time(Some(request), &api_key)
+        .await
+        .unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: MarketSalesOverTimeResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
@@ -1,46 +1,160 @@
+//! # Marketplace Stats
+//!
+//! POST `https://rest-api.hellomoon.io/v0/nft/marketplace/stats`
+//!
+//! Trading activity per marketplace for each day, week or month: volume, number of sales,
+//! distinct buyers and sellers and the fees collected. Amounts are in lamports.
+use crate::dimension_facts::nft::secondary_sales::{serialize_marketplace_filter, Marketplace};
+use crate::summary::common::{DateRange, Granularity};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
 use serde::{Deserialize, Serialize};
-
-use crate::{core_call, limit_is_zero, page_is_zero};
-
-const API_URL: &str = "";
+use std::collections::BTreeMap;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
-pub struct Request {
+pub struct MarketplacesStatsRequest {
+    /// Only the stats of this marketplace, every marketplace by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_marketplace_filter")]
+    pub marketplace: Option<Marketplace>,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the sales are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
     #[serde(skip_serializing_if = "limit_is_zero")]
-    limit: usize,
+    pub limit: usize,
+    /// The page number to return
     #[serde(skip_serializing_if = "page_is_zero")]
-    page: usize,
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
     #[serde(skip_serializing_if = "String::is_empty")]
-    pagination_token: String,
+    pub pagination_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Response {
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct MarketplacesStatsResponse {
     /// array of objects
-    data: Option<Vec<IResponse>>,
+    pub data: Option<Vec<MarketplaceStats>>,
+    /// The pagination token to use to keep your position in the results
     #[serde(rename = "paginationToken")]
-    pagination_token: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct MarketplaceStats {
+    /// The marketplace the sales happened on
+    pub marketplace: Option<Marketplace>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Total price of the sales, in lamports
+    pub volume: Option<u64>,
+    /// Number of sales
+    #[serde(rename = "salesCount")]
+    pub sales_count: Option<u64>,
+    /// Number of distinct buyers
+    #[serde(rename = "uniqueBuyers")]
+    pub unique_buyers: Option<u64>,
+    /// Number of distinct sellers
+    #[serde(rename = "uniqueSellers")]
+    pub unique_sellers: Option<u64>,
+    /// Fees collected by the marketplace, in lamports
+    #[serde(rename = "marketplaceFees")]
+    pub marketplace_fees: Option<u64>,
+    /// Royalties paid to the creators, in lamports
+    #[serde(rename = "royaltiesPaid")]
+    pub royalties_paid: Option<u64>,
+}
+
+impl MarketplaceStats {
+    pub fn volume_sol(&self) -> Option<f64> {
+        self.volume.map(lamports_to_sol)
+    }
+
+    pub fn marketplace_fees_sol(&self) -> Option<f64> {
+        self.marketplace_fees.map(lamports_to_sol)
+    }
+
+    /// Average sale price, in lamports.
+    pub fn average_price(&self) -> Option<u64> {
+        let sales_count = self.sales_count.filter(|sales_count| *sales_count > 0)?;
+        Some(self.volume? / sales_count)
+    }
+
+    /// Fees collected as a share of the volume, between 0 and 1.
+    pub fn fee_rate(&self) -> Option<f64> {
+        let volume = self.volume.filter(|volume| *volume > 0)?;
+        Some(self.marketplace_fees? as f64 / volume as f64)
+    }
+}
+
+/// Share of the volume of each marketplace over all `rows`, between 0 and 1, largest first.
+pub fn market_share(rows: &[MarketplaceStats]) -> Vec<(Marketplace, f64)> {
+    let mut volumes: BTreeMap<Marketplace, u64> = BTreeMap::new();
+    for row in rows {
+        if let Some(marketplace) = row.marketplace {
+            *volumes.entry(marketplace).or_default() += row.volume.unwrap_or_default();
+        }
+    }
+    let total: u64 = volumes.values().sum();
+    if total == 0 {
+        return Vec::new();
+    }
+    let mut shares: Vec<(Marketplace, f64)> = volumes
+        .into_iter()
+        .map(|(marketplace, volume)| (marketplace, volume as f64 / total as f64))
+        .collect();
+    shares.sort_by(|a, b| b.1.total_cmp(&a.1));
+    shares
+}
+
+pub async fn marketplaces_stats(
+    request: Option<MarketplacesStatsRequest>,
+    api_key: &str,
+) -> anyhow::Result<MarketplacesStatsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/nft/marketplace/stats");
+    core_call::<MarketplacesStatsRequest, MarketplacesStatsResponse>(request, api_url, api_key)
+        .await
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct IResponse {}
+#[test]
+fn test_market_share() {
+    // TENSOR is unknown to this crate
+    let response: MarketplacesStatsResponse =
+        crate::fixture("marketplaces_stats/marketplaces_stats");
+    let rows = response.data.unwrap();
+    assert_eq!(rows[3].marketplace, Some(Marketplace::Other));
 
-pub async fn example(request: Option<Request>, api_key: &str) -> anyhow::Result<Response> {
-    core_call::<Request, Response>(request, API_URL.to_string(), api_key).await
+    assert_eq!(
+        market_share(&rows),
+        vec![
+            (Marketplace::Mev2, 0.8),
+            (Marketplace::Hadeswap, 0.1),
+            (Marketplace::Other, 0.1),
+        ]
+    );
+    assert_eq!(rows[0].average_price(), Some(2_000_000_000));
+    assert_eq!(rows[0].fee_rate(), Some(0.02));
 }
 
 #[tokio::test]
-#[ignore]
-async fn test_example() {
-    let request = Request::default();
+#[ignore = "reason"]
+async fn test_marketplaces_stats() {
+    let request = MarketplacesStatsRequest {
+        marketplace: Some(Marketplace::Mev2),
+        day: Some(DateRange::since("2023-01-01")),
+        ..Default::default()
+    };
 
     let api_key = dotenv::var("api_keys").unwrap();
 
-    let left = example(Some(request), &api_key).await.unwrap();
+    let left = marketplaces_stats(Some(request), &api_key).await.unwrap();
 
     let r = serde_json::to_string_pretty(&left).unwrap();
-    let right: Response = serde_json::from_str(&r).unwrap();
+    let right: MarketplacesStatsResponse = serde_json::from_str(&r).unwrap();
     println!("{:#?}", right);
     assert_eq!(left, right);
 }
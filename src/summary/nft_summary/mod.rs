@@ -9,5 +9,9 @@ pub mod collection_stats;
 pub mod collection_top_holder;
 pub mod collection_washtrading_index;
 pub mod cumulative_nft_owners_over_time;
+pub mod frakt_bonds_summary;
+pub mod frakt_pairs_summary;
 pub mod market_sales_over_time;
 pub mod marketplaces_stats;
+pub mod sharky_default_stats;
+pub mod sharky_historical_defaults;
@@ -0,0 +1,147 @@
+//! # Sharky Default Stats
+//!
+//! POST `https://rest-api.hellomoon.io/v0/sharky/default-stats`
+//!
+//! Loans taken, repaid and defaulted on Sharky since the launch of each order book, one row per
+//! collection and loan duration. Amounts are in lamports.
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, lamports_to_sol, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SharkyDefaultStatsRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the loans of this duration, in seconds
+    #[serde(rename = "durationSeconds")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<u64>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SharkyDefaultStatsResponse {
+    /// array of objects
+    pub data: Option<Vec<SharkyDefaultStats>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SharkyDefaultStats {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// The address of the order book of the collection
+    #[serde(rename = "orderBook")]
+    pub order_book: Option<String>,
+    /// Duration of the loans, in seconds
+    #[serde(rename = "durationSeconds")]
+    pub duration_seconds: Option<u64>,
+    /// Number of loans taken
+    #[serde(rename = "loansTaken")]
+    pub loans_taken: Option<u64>,
+    /// Number of loans repaid, extensions included
+    #[serde(rename = "loansRepaid")]
+    pub loans_repaid: Option<u64>,
+    /// Number of loans foreclosed
+    #[serde(rename = "loansDefaulted")]
+    pub loans_defaulted: Option<u64>,
+    /// Amount lent, in lamports
+    pub volume: Option<u64>,
+    /// Amount lent on the loans foreclosed, in lamports
+    #[serde(rename = "defaultedVolume")]
+    pub defaulted_volume: Option<u64>,
+}
+
+impl SharkyDefaultStats {
+    pub fn defaulted_volume_sol(&self) -> Option<f64> {
+        self.defaulted_volume.map(lamports_to_sol)
+    }
+
+    /// Share of the loans closed that were foreclosed, between 0 and 1.
+    pub fn default_rate(&self) -> Option<f64> {
+        let defaulted = self.loans_defaulted?;
+        let closed = defaulted + self.loans_repaid?;
+        (closed > 0).then(|| defaulted as f64 / closed as f64)
+    }
+}
+
+/// Default rate of the loans of `rows` per loan duration in seconds, between 0 and 1.
+///
+/// Rows of the same duration, e.g. of several collections, are added up before the rate is
+/// computed. Durations without a closed loan are left out.
+pub fn default_rate_by_duration(rows: &[SharkyDefaultStats]) -> BTreeMap<u64, f64> {
+    let mut closed: BTreeMap<u64, (u64, u64)> = BTreeMap::new();
+    for row in rows {
+        let Some(duration_seconds) = row.duration_seconds else {
+            continue;
+        };
+        let (defaulted, repaid) = closed.entry(duration_seconds).or_default();
+        *defaulted += row.loans_defaulted.unwrap_or_default();
+        *repaid += row.loans_repaid.unwrap_or_default();
+    }
+    closed
+        .into_iter()
+        .filter(|(_, (defaulted, repaid))| defaulted + repaid > 0)
+        .map(|(duration_seconds, (defaulted, repaid))| {
+            (
+                duration_seconds,
+                defaulted as f64 / (defaulted + repaid) as f64,
+            )
+        })
+        .collect()
+}
+
+pub async fn sharky_default_stats(
+    request: Option<SharkyDefaultStatsRequest>,
+    api_key: &str,
+) -> anyhow::Result<SharkyDefaultStatsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/sharky/default-stats");
+    core_call::<SharkyDefaultStatsRequest, SharkyDefaultStatsResponse>(request, api_url, api_key)
+        .await
+}
+
+#[test]
+fn test_default_rate_by_duration() {
+    let response: SharkyDefaultStatsResponse =
+        crate::fixture("sharky_default_stats/sharky_default_stats");
+    let rows = response.data.unwrap();
+
+    assert_eq!(rows[0].default_rate(), Some(0.1));
+    assert_eq!(rows[0].defaulted_volume_sol(), Some(25.0));
+    assert_eq!(
+        default_rate_by_duration(&rows),
+        BTreeMap::from([(86400 * 7, 0.1), (86400 * 14, 0.25)])
+    );
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_sharky_default_stats() {
+    let request = SharkyDefaultStatsRequest::default();
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = sharky_default_stats(Some(request), &api_key).await.unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: SharkyDefaultStatsResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}
@@ -0,0 +1,202 @@
+//! # Sharky Historical Defaults
+//!
+//! POST `https://rest-api.hellomoon.io/v0/sharky/historical-defaults`
+//!
+//! Sharky loans that ended in each day, week or month, repaid or foreclosed, one row per
+//! collection and loan duration. Amounts are in lamports.
+use crate::fetch_all_pages;
+use crate::summary::common::{DateRange, Granularity, TimePoint, TimeSeries};
+use crate::HELLOMOON_ROOT_URL;
+use crate::{core_call, limit_is_zero, page_is_zero};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SharkyHistoricalDefaultsRequest {
+    /// To find the correct helloMoonCollectionId,
+    /// click here and search a collection name.
+    /// This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hello_moon_collection_id: String,
+    /// Only the loans of this duration, in seconds
+    #[serde(rename = "durationSeconds")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<u64>,
+    /// Only the periods starting on a day matching the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<DateRange>,
+    /// The period the loans are aggregated over, one day by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    /// The number of results to return per page
+    #[serde(skip_serializing_if = "limit_is_zero")]
+    pub limit: usize,
+    /// The page number to return
+    #[serde(skip_serializing_if = "page_is_zero")]
+    pub page: usize,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagination_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct SharkyHistoricalDefaultsResponse {
+    /// array of objects
+    pub data: Option<Vec<SharkyHistoricalDefaults>>,
+    /// The pagination token to use to keep your position in the results
+    #[serde(rename = "paginationToken")]
+    pub pagination_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SharkyHistoricalDefaults {
+    /// To find the correct helloMoonCollectionId, click here and search a collection name. This list is continuously updated.
+    #[serde(rename = "helloMoonCollectionId")]
+    pub hello_moon_collection_id: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`
+    pub day: Option<String>,
+    /// Duration of the loans, in seconds
+    #[serde(rename = "durationSeconds")]
+    pub duration_seconds: Option<u64>,
+    /// Number of loans repaid in the period, extensions included
+    #[serde(rename = "loansRepaid")]
+    pub loans_repaid: Option<u64>,
+    /// Number of loans foreclosed in the period
+    #[serde(rename = "loansDefaulted")]
+    pub loans_defaulted: Option<u64>,
+    /// Amount lent on the loans foreclosed, in lamports
+    #[serde(rename = "defaultedVolume")]
+    pub defaulted_volume: Option<u64>,
+}
+
+impl SharkyHistoricalDefaults {
+    /// Share of the loans ended in the period that were foreclosed, between 0 and 1.
+    pub fn default_rate(&self) -> Option<f64> {
+        let defaulted = self.loans_defaulted?;
+        let closed = defaulted + self.loans_repaid?;
+        (closed > 0).then(|| defaulted as f64 / closed as f64)
+    }
+}
+
+/// Add up the rows of each day, e.g. of several loan durations, into one series.
+///
+/// The collection and duration of a point are kept only when all its rows share them.
+pub fn historical_defaults_series(
+    granularity: Granularity,
+    rows: impl IntoIterator<Item = SharkyHistoricalDefaults>,
+) -> TimeSeries<SharkyHistoricalDefaults> {
+    let mut days: BTreeMap<String, SharkyHistoricalDefaults> = BTreeMap::new();
+    for row in rows {
+        let Some(day) = row.day.clone() else {
+            continue;
+        };
+        let Some(total) = days.get_mut(&day) else {
+            days.insert(day, row);
+            continue;
+        };
+        let add = |a: Option<u64>, b: Option<u64>| match (a, b) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or_default() + b.unwrap_or_default()),
+        };
+        total.loans_repaid = add(total.loans_repaid, row.loans_repaid);
+        total.loans_defaulted = add(total.loans_defaulted, row.loans_defaulted);
+        total.defaulted_volume = add(total.defaulted_volume, row.defaulted_volume);
+        if total.hello_moon_collection_id != row.hello_moon_collection_id {
+            total.hello_moon_collection_id = None;
+        }
+        if total.duration_seconds != row.duration_seconds {
+            total.duration_seconds = None;
+        }
+    }
+    TimeSeries::new(
+        granularity,
+        days.into_iter()
+            .map(|(day, value)| TimePoint { day, value }),
+    )
+}
+
+pub async fn sharky_historical_defaults(
+    request: Option<SharkyHistoricalDefaultsRequest>,
+    api_key: &str,
+) -> anyhow::Result<SharkyHistoricalDefaultsResponse> {
+    let api_url = format!("{}{}", HELLOMOON_ROOT_URL, "/sharky/historical-defaults");
+    core_call::<SharkyHistoricalDefaultsRequest, SharkyHistoricalDefaultsResponse>(
+        request, api_url, api_key,
+    )
+    .await
+}
+
+/// Sharky loans of a collection ended per period matching `day`, of every loan duration or
+/// only of `duration_seconds`.
+///
+/// The default rate per period is `series.map(|row| row.default_rate())`.
+pub async fn sharky_historical_defaults_time_series(
+    api_key: &str,
+    hello_moon_collection_id: &str,
+    duration_seconds: Option<u64>,
+    granularity: Granularity,
+    day: Option<DateRange>,
+) -> anyhow::Result<TimeSeries<SharkyHistoricalDefaults>> {
+    let rows = fetch_all_pages(|pagination_token| {
+        let request = SharkyHistoricalDefaultsRequest {
+            hello_moon_collection_id: hello_moon_collection_id.to_string(),
+            duration_seconds,
+            day: day.clone(),
+            granularity: Some(granularity),
+            pagination_token,
+            ..Default::default()
+        };
+        async move {
+            let response = sharky_historical_defaults(Some(request), api_key).await?;
+            Ok((response.data, response.pagination_token))
+        }
+    })
+    .await?;
+    Ok(historical_defaults_series(granularity, rows))
+}
+
+#[test]
+fn test_historical_defaults_series() {
+    let response: SharkyHistoricalDefaultsResponse =
+        crate::fixture("sharky_historical_defaults/sharky_historical_defaults");
+    let rows = response.data.unwrap();
+
+    let series = historical_defaults_series(Granularity::OneDay, rows);
+    assert_eq!(series.len(), 2);
+    let first = &series.points[0].value;
+    assert_eq!(first.loans_repaid, Some(15));
+    assert_eq!(first.loans_defaulted, Some(5));
+    assert_eq!(first.duration_seconds, None);
+    assert_eq!(
+        first.hello_moon_collection_id.as_deref(),
+        Some("040de757c0d2b75dcee999ddd47689c4")
+    );
+    assert_eq!(first.defaulted_volume, Some(12_500_000_000));
+
+    let default_rates = series.map(|row| row.default_rate());
+    assert_eq!(default_rates.get("2023-01-01"), Some(&Some(0.25)));
+    assert_eq!(default_rates.get("2023-01-02"), Some(&Some(0.2)));
+}
+
+#[tokio::test]
+#[ignore = "reason"]
+async fn test_sharky_historical_defaults() {
+    let request = SharkyHistoricalDefaultsRequest {
+        day: Some(DateRange::since("2023-01-01")),
+        granularity: Some(Granularity::OneWeek),
+        ..Default::default()
+    };
+
+    let api_key = dotenv::var("api_keys").unwrap();
+
+    let left = sharky_historical_defaults(Some(request), &api_key)
+        .await
+        .unwrap();
+
+    let r = serde_json::to_string_pretty(&left).unwrap();
+    let right: SharkyHistoricalDefaultsResponse = serde_json::from_str(&r).unwrap();
+    println!("{:#?}", right);
+    assert_eq!(left, right);
+}